@@ -0,0 +1,274 @@
+use std::collections::HashSet;
+
+use super::geometry::GeometryAnalyzer;
+use crate::model::shape::{Chart, Point};
+
+#[derive(Debug)]
+pub struct DimensionEstimate {
+    pub dimension: f64,
+    // coefficient of determination of the log-log fit
+    pub r_squared: f64,
+    // (log scale, log measure) pairs used for the fit
+    pub samples: Vec<(f64, f64)>,
+}
+
+pub struct BoxCountingConfig {
+    // box sizes run from extent / 2^min_level down to extent / 2^max_level
+    pub min_level: u32,
+    pub max_level: u32,
+}
+
+pub struct CorrelationConfig {
+    pub max_points: usize,
+    // radii run from extent / 2^min_level down to extent / 2^max_level
+    pub min_level: u32,
+    pub max_level: u32,
+}
+
+pub struct DimensionEstimator;
+
+impl DimensionEstimator {
+    fn fit(samples: Vec<(f64, f64)>) -> Option<DimensionEstimate> {
+        if samples.len() < 2 {
+            return None;
+        }
+        let count = samples.len() as f64;
+        let mean_x = samples.iter().map(|s| s.0).sum::<f64>() / count;
+        let mean_y = samples.iter().map(|s| s.1).sum::<f64>() / count;
+        let sxx: f64 = samples.iter().map(|s| (s.0 - mean_x).powi(2)).sum();
+        let sxy: f64 = samples
+            .iter()
+            .map(|s| (s.0 - mean_x) * (s.1 - mean_y))
+            .sum();
+        let syy: f64 = samples.iter().map(|s| (s.1 - mean_y).powi(2)).sum();
+        if sxx == 0.0 {
+            return None;
+        }
+        let slope = sxy / sxx;
+        let r_squared = if syy == 0.0 {
+            1.0
+        } else {
+            (sxy * sxy) / (sxx * syy)
+        };
+        Some(DimensionEstimate {
+            dimension: slope,
+            r_squared,
+            samples,
+        })
+    }
+
+    // grid cells of size 1 crossed by the segment, in cell coordinates
+    fn traverse(start: &Point, end: &Point, cells: &mut HashSet<(i64, i64)>) {
+        let mut cell = (start.x.floor() as i64, start.y.floor() as i64);
+        let last = (end.x.floor() as i64, end.y.floor() as i64);
+        cells.insert(cell);
+        let (dx, dy) = (end.x - start.x, end.y - start.y);
+        let step = (dx.signum() as i64, dy.signum() as i64);
+        let boundary = |position: f64, cell: i64, step: i64| -> f64 {
+            if step > 0 {
+                (cell + 1) as f64 - position
+            } else {
+                position - cell as f64
+            }
+        };
+        let mut t_max_x = if dx == 0.0 {
+            f64::INFINITY
+        } else {
+            boundary(start.x, cell.0, step.0) / dx.abs()
+        };
+        let mut t_max_y = if dy == 0.0 {
+            f64::INFINITY
+        } else {
+            boundary(start.y, cell.1, step.1) / dy.abs()
+        };
+        let t_delta_x = 1.0 / dx.abs();
+        let t_delta_y = 1.0 / dy.abs();
+        let steps = (last.0 - cell.0).abs() + (last.1 - cell.1).abs();
+        for _ in 0..steps {
+            if t_max_x < t_max_y {
+                cell.0 += step.0;
+                t_max_x += t_delta_x;
+            } else {
+                cell.1 += step.1;
+                t_max_y += t_delta_y;
+            }
+            cells.insert(cell);
+        }
+    }
+
+    pub fn box_counting(chart: &Chart, config: &BoxCountingConfig) -> Option<DimensionEstimate> {
+        let bounds = GeometryAnalyzer::bounds(&chart.points)?;
+        let extent = bounds.width().max(bounds.height());
+        if extent == 0.0 {
+            return None;
+        }
+        let segments = GeometryAnalyzer::segments(chart);
+        let samples = (config.min_level..=config.max_level)
+            .map(|level| {
+                let size = extent / 2_f64.powi(level as i32);
+                let to_cell = |p: &Point| Point {
+                    x: (p.x - bounds.min.x) / size,
+                    y: (p.y - bounds.min.y) / size,
+                };
+                let mut cells: HashSet<(i64, i64)> = HashSet::new();
+                for (start, end) in &segments {
+                    DimensionEstimator::traverse(&to_cell(start), &to_cell(end), &mut cells);
+                }
+                ((1.0 / size).ln(), (cells.len() as f64).ln())
+            })
+            .collect();
+        DimensionEstimator::fit(samples)
+    }
+
+    // Grassberger-Procaccia estimate over the chart points, thinned evenly to `max_points`
+    pub fn correlation(chart: &Chart, config: &CorrelationConfig) -> Option<DimensionEstimate> {
+        let bounds = GeometryAnalyzer::bounds(&chart.points)?;
+        let extent = bounds.width().max(bounds.height());
+        let stride = chart.points.len().div_ceil(config.max_points.max(1));
+        let points: Vec<&Point> = chart.points.iter().step_by(stride.max(1)).collect();
+        let mut distances: Vec<f64> = points
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| {
+                points[i + 1..]
+                    .iter()
+                    .map(move |b| (a.x - b.x).hypot(a.y - b.y))
+            })
+            .collect();
+        if distances.is_empty() || extent == 0.0 {
+            return None;
+        }
+        distances.sort_by(f64::total_cmp);
+        let pair_count = distances.len() as f64;
+        let samples = (config.min_level..=config.max_level)
+            .filter_map(|level| {
+                let radius = extent / 2_f64.powi(level as i32);
+                let within = distances.partition_point(|&d| d < radius);
+                if within == 0 {
+                    return None;
+                }
+                Some((radius.ln(), (within as f64 / pair_count).ln()))
+            })
+            .collect();
+        DimensionEstimator::fit(samples)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::shape::{BaseChartConfig, ChartKind, Mutation, Order, Randomizer},
+        repository::shapes::kinds::selector::ShapesGeneratorSelector,
+    };
+
+    fn generate(kind: ChartKind, complexity: u64) -> Chart {
+        ShapesGeneratorSelector::select(&kind).generate_chart(BaseChartConfig {
+            kind,
+            complexity,
+            mutation: Some(Mutation {
+                size: 1.0,
+                angle: 1.0,
+            }),
+            randomizer: Some(Randomizer {
+                size_amplitude: 0.0,
+                size_seed: 0,
+                angle_amplitude: 0.0,
+                angle_seed: 0,
+            }),
+            parameters: None,
+        })
+    }
+
+    #[test]
+    fn test_line_box_counting() {
+        let chart = Chart {
+            points: vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.3 }],
+            orders: vec![Order { link: (0, 1) }],
+        };
+        let actual = DimensionEstimator::box_counting(
+            &chart,
+            &BoxCountingConfig {
+                min_level: 2,
+                max_level: 8,
+            },
+        )
+        .unwrap();
+        assert!((actual.dimension - 1.0).abs() < 0.05);
+        assert!(actual.r_squared > 0.999);
+    }
+
+    #[test]
+    fn test_koch_curve_box_counting() {
+        // log 4 / log 3
+        let chart = generate(ChartKind::KochCurve, 7);
+        let actual = DimensionEstimator::box_counting(
+            &chart,
+            &BoxCountingConfig {
+                min_level: 3,
+                max_level: 8,
+            },
+        )
+        .unwrap();
+        assert!((actual.dimension - 1.2619).abs() < 0.05);
+        assert!(actual.r_squared > 0.99);
+    }
+
+    #[test]
+    fn test_koch_curve_correlation() {
+        let chart = generate(ChartKind::KochCurve, 7);
+        let actual = DimensionEstimator::correlation(
+            &chart,
+            &CorrelationConfig {
+                max_points: 2000,
+                min_level: 3,
+                max_level: 7,
+            },
+        )
+        .unwrap();
+        assert!((actual.dimension - 1.2619).abs() < 0.05);
+        assert!(actual.r_squared > 0.99);
+    }
+
+    #[test]
+    fn test_dragon_box_counting() {
+        // the dragon fills an area; its boundary, of dimension about 1.52, still adds boxes at these
+        // scales, so the estimate approaches 2 from below as the curve and the boxes get finer
+        let config = BoxCountingConfig {
+            min_level: 5,
+            max_level: 9,
+        };
+        let coarse =
+            DimensionEstimator::box_counting(&generate(ChartKind::FoldDragon, 16), &config)
+                .unwrap();
+        let actual =
+            DimensionEstimator::box_counting(&generate(ChartKind::FoldDragon, 20), &config)
+                .unwrap();
+        assert!(actual.dimension > coarse.dimension);
+        assert!(
+            (actual.dimension - 2.0).abs() < 0.15,
+            "{}",
+            actual.dimension
+        );
+        assert!(actual.r_squared > 0.999);
+    }
+
+    #[test]
+    fn test_tri_trans_box_counting() {
+        // every fold replaces a segment with four of half its length, so the trans Tri curve has
+        // similarity dimension log 4 / log 2 = 2 and fills an area; the 1.5 asked for in the
+        // request does not hold for this curve, and the estimator agrees with the similarity value
+        let chart = generate(ChartKind::TriTrans, 10);
+        assert_eq!(4_usize.pow(9), chart.orders.len());
+        let actual = DimensionEstimator::box_counting(
+            &chart,
+            &BoxCountingConfig {
+                min_level: 4,
+                max_level: 8,
+            },
+        )
+        .unwrap();
+        assert!((actual.dimension - 2.0).abs() < 0.1, "{}", actual.dimension);
+        assert!(actual.r_squared > 0.999);
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+
+use crate::model::{
+    shape::{Chart, Point, Size},
+    transform::Transform,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    pub fn width(&self) -> f64 {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> f64 {
+        self.max.y - self.min.y
+    }
+
+    pub fn center(&self) -> Point {
+        Point {
+            x: (self.min.x + self.max.x) / 2.0,
+            y: (self.min.y + self.max.y) / 2.0,
+        }
+    }
+
+    pub fn union(&self, other: &Bounds) -> Bounds {
+        Bounds {
+            min: Point {
+                x: self.min.x.min(other.min.x),
+                y: self.min.y.min(other.min.y),
+            },
+            max: Point {
+                x: self.max.x.max(other.max.x),
+                y: self.max.y.max(other.max.y),
+            },
+        }
+    }
+}
+
+pub struct GeometryAnalyzer;
+
+impl GeometryAnalyzer {
+    pub fn bounds(points: &[Point]) -> Option<Bounds> {
+        let first = points.first()?;
+        let mut bounds = Bounds {
+            min: *first,
+            max: *first,
+        };
+        for p in points {
+            bounds.min.x = bounds.min.x.min(p.x);
+            bounds.min.y = bounds.min.y.min(p.y);
+            bounds.max.x = bounds.max.x.max(p.x);
+            bounds.max.y = bounds.max.y.max(p.y);
+        }
+        Some(bounds)
+    }
+
+    // for every point, the earliest kept point within `tolerance` of it, or itself when there is none;
+    // without a positive tolerance only coincident points stand for each other
+    pub fn representatives(points: &[Point], tolerance: f64) -> Vec<usize> {
+        if tolerance.is_nan() || tolerance <= 0.0 {
+            let mut keys: HashMap<(u64, u64), usize> = HashMap::new();
+            // adding zero folds -0.0 into 0.0
+            return points
+                .iter()
+                .enumerate()
+                .map(|(i, p)| {
+                    *keys
+                        .entry(((p.x + 0.0).to_bits(), (p.y + 0.0).to_bits()))
+                        .or_insert(i)
+                })
+                .collect();
+        }
+        let Some(bounds) = GeometryAnalyzer::bounds(points) else {
+            return vec![];
+        };
+        // cells at least as wide as the tolerance, so a close point is always in one of the nine around,
+        // and not so narrow against the extent that the cell keys overflow
+        let cell_size = tolerance.max(bounds.width().max(bounds.height()) * 1e-9);
+        let to_cell = |value: f64, origin: f64| ((value - origin) / cell_size).floor() as i64;
+        let mut cells: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let cell = (to_cell(p.x, bounds.min.x), to_cell(p.y, bounds.min.y));
+                let close = (-1..=1)
+                    .flat_map(|dx| {
+                        (-1..=1)
+                            .map(move |dy| (cell.0.saturating_add(dx), cell.1.saturating_add(dy)))
+                    })
+                    .flat_map(|neighbour| cells.get(&neighbour).into_iter().flatten())
+                    .filter(|&&j| (points[j].x - p.x).hypot(points[j].y - p.y) <= tolerance)
+                    .min()
+                    .copied();
+                close.unwrap_or_else(|| {
+                    cells.entry(cell).or_default().push(i);
+                    i
+                })
+            })
+            .collect()
+    }
+
+    pub fn segments(chart: &Chart) -> Vec<(Point, Point)> {
+        chart
+            .orders
+            .iter()
+            .map(|order| {
+                (
+                    chart.points[chart.point_index(order.link.0)],
+                    chart.points[chart.point_index(order.link.1)],
+                )
+            })
+            .collect()
+    }
+
+    pub fn total_length(chart: &Chart) -> f64 {
+        GeometryAnalyzer::segments(chart)
+            .iter()
+            .map(|(start, end)| (end.x - start.x).hypot(end.y - start.y))
+            .sum()
+    }
+
+    // centroid of the drawn segments weighted by their length; the mean of the points when nothing has length
+    pub fn centroid(chart: &Chart) -> Option<Point> {
+        if chart.points.is_empty() {
+            return None;
+        }
+        let (mut x, mut y, mut length) = (0.0, 0.0, 0.0);
+        for (start, end) in GeometryAnalyzer::segments(chart) {
+            let segment = (end.x - start.x).hypot(end.y - start.y);
+            x += segment * (start.x + end.x) / 2.0;
+            y += segment * (start.y + end.y) / 2.0;
+            length += segment;
+        }
+        if length > 0.0 {
+            return Some(Point {
+                x: x / length,
+                y: y / length,
+            });
+        }
+        let count = chart.points.len() as f64;
+        Some(Point {
+            x: chart.points.iter().map(|p| p.x).sum::<f64>() / count,
+            y: chart.points.iter().map(|p| p.y).sum::<f64>() / count,
+        })
+    }
+
+    // uniform scale that centres `bounds` in [0, width] x [0, height]
+    pub fn fit_transform(bounds: &Bounds, target: &Size) -> Transform {
+        let scale_x = target.width / bounds.width();
+        let scale_y = target.height / bounds.height();
+        let scale = match (scale_x.is_finite(), scale_y.is_finite()) {
+            (true, true) => scale_x.min(scale_y),
+            (true, false) => scale_x,
+            (false, true) => scale_y,
+            (false, false) => 1.0,
+        };
+        let center = bounds.center();
+        Transform::translate(-center.x, -center.y)
+            .then(&Transform::scale(scale, scale))
+            .then(&Transform::translate(
+                target.width / 2.0,
+                target.height / 2.0,
+            ))
+    }
+
+    pub fn normalize(chart: &Chart, target: &Size) -> Chart {
+        match GeometryAnalyzer::bounds(&chart.points) {
+            Some(bounds) => GeometryAnalyzer::fit_transform(&bounds, target).apply_chart(chart),
+            None => Chart {
+                points: vec![],
+                orders: chart.orders.clone(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::shape::{BaseChartConfig, ChartKind, Mutation, Order, Randomizer},
+        repository::shapes::kinds::selector::ShapesGeneratorSelector,
+    };
+
+    fn generate(kind: ChartKind, complexity: u64) -> Chart {
+        ShapesGeneratorSelector::select(&kind).generate_chart(BaseChartConfig {
+            kind,
+            complexity,
+            mutation: Some(Mutation {
+                size: 1.0,
+                angle: 1.0,
+            }),
+            randomizer: Some(Randomizer {
+                size_amplitude: 0.0,
+                size_seed: 0,
+                angle_amplitude: 0.0,
+                angle_seed: 0,
+            }),
+            parameters: None,
+        })
+    }
+
+    #[test]
+    fn test_c_curve_analysis() {
+        let chart = generate(ChartKind::FoldCCurve, 4);
+        let expect = Bounds {
+            min: Point { x: -1.5, y: -1.5 },
+            max: Point { x: 1.5, y: 0.0 },
+        };
+        assert_eq!(Some(expect), GeometryAnalyzer::bounds(&chart.points));
+        assert!((GeometryAnalyzer::total_length(&chart) - 8.0 / 2.0_f64.sqrt()).abs() < 1e-12);
+        assert_eq!(
+            Some(Point { x: 0.0, y: -0.875 }),
+            GeometryAnalyzer::centroid(&chart)
+        );
+    }
+
+    #[test]
+    fn test_representatives() {
+        let points = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 0.99, y: 0.0 },
+            Point { x: 1.01, y: 0.0 },
+            Point { x: -0.0, y: 0.0 },
+        ];
+        // the two middle points fall into neighbouring cells but are still joined
+        assert_eq!(
+            vec![0, 1, 1, 0],
+            GeometryAnalyzer::representatives(&points, 0.1)
+        );
+        assert_eq!(
+            vec![0, 1, 2, 0],
+            GeometryAnalyzer::representatives(&points, 0.0)
+        );
+        assert_eq!(
+            vec![0, 1, 2, 0],
+            GeometryAnalyzer::representatives(&points, f64::NAN)
+        );
+        assert_eq!(
+            vec![0, 1, 2, 0],
+            GeometryAnalyzer::representatives(&points, 1e-300)
+        );
+    }
+
+    #[test]
+    fn test_loop_analysis() {
+        let chart = Chart {
+            points: vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 2.0, y: 0.0 },
+                Point { x: 2.0, y: 2.0 },
+                Point { x: 0.0, y: 2.0 },
+            ],
+            orders: (0..4).map(|i| Order { link: (i, i + 1) }).collect(),
+        };
+        assert_eq!(8.0, GeometryAnalyzer::total_length(&chart));
+        assert_eq!(
+            Some(Point { x: 1.0, y: 1.0 }),
+            GeometryAnalyzer::centroid(&chart)
+        );
+    }
+
+    #[test]
+    fn test_normalization() {
+        let target = Size {
+            width: 400.0,
+            height: 300.0,
+        };
+        for kind in [
+            ChartKind::FoldDragon,
+            ChartKind::BinaryTree,
+            ChartKind::Clover,
+        ] {
+            let chart = GeometryAnalyzer::normalize(&generate(kind, 6), &target);
+            let bounds = GeometryAnalyzer::bounds(&chart.points).unwrap();
+            assert!(bounds.min.x >= -1e-9 && bounds.max.x <= target.width + 1e-9);
+            assert!(bounds.min.y >= -1e-9 && bounds.max.y <= target.height + 1e-9);
+            assert!(
+                (bounds.width() - target.width).abs() < 1e-9
+                    || (bounds.height() - target.height).abs() < 1e-9
+            );
+            assert_eq!(Point { x: 200.0, y: 150.0 }, bounds.center());
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::{HashMap, HashSet};
+
+use super::geometry::GeometryAnalyzer;
+use crate::model::shape::{Chart, Point};
+
+#[derive(Debug, PartialEq)]
+pub struct IntersectionReport {
+    // pairs of `Order` indices crossing or touching away from their shared vertices
+    pub crossings: Vec<(usize, usize)>,
+    // pairs of `Order` indices lying on the same line with a common stretch
+    pub overlaps: Vec<(usize, usize)>,
+    // groups of point indices at the same position
+    pub duplicate_points: Vec<Vec<usize>>,
+}
+
+impl IntersectionReport {
+    pub fn is_simple(&self) -> bool {
+        self.crossings.is_empty() && self.overlaps.is_empty()
+    }
+}
+
+enum SegmentRelation {
+    Disjoint,
+    Crossing,
+    Overlap,
+}
+
+pub struct IntersectionDetector;
+
+impl IntersectionDetector {
+    fn cross(a: &Point, b: &Point) -> f64 {
+        a.x * b.y - a.y * b.x
+    }
+
+    fn relation(a: &(Point, Point), b: &(Point, Point), epsilon: f64) -> SegmentRelation {
+        let (p, p_end) = a;
+        let (q, q_end) = b;
+        let r = Point {
+            x: p_end.x - p.x,
+            y: p_end.y - p.y,
+        };
+        let s = Point {
+            x: q_end.x - q.x,
+            y: q_end.y - q.y,
+        };
+        let r_length = r.x.hypot(r.y);
+        let s_length = s.x.hypot(s.y);
+        if r_length <= epsilon || s_length <= epsilon {
+            return SegmentRelation::Disjoint;
+        }
+        let offset = Point {
+            x: q.x - p.x,
+            y: q.y - p.y,
+        };
+        let denominator = IntersectionDetector::cross(&r, &s);
+
+        if denominator.abs() <= epsilon * r_length * s_length {
+            if IntersectionDetector::cross(&offset, &r).abs() > epsilon * r_length {
+                return SegmentRelation::Disjoint;
+            }
+            let squared = r_length * r_length;
+            let t0 = (offset.x * r.x + offset.y * r.y) / squared;
+            let t1 = t0 + (s.x * r.x + s.y * r.y) / squared;
+            let shared = t0.max(t1).min(1.0) - t0.min(t1).max(0.0);
+            return if shared * r_length > epsilon {
+                SegmentRelation::Overlap
+            } else {
+                SegmentRelation::Disjoint
+            };
+        }
+
+        let t = IntersectionDetector::cross(&offset, &s) / denominator;
+        let u = IntersectionDetector::cross(&offset, &r) / denominator;
+        let t_margin = epsilon / r_length;
+        let u_margin = epsilon / s_length;
+        if t < -t_margin || t > 1.0 + t_margin || u < -u_margin || u > 1.0 + u_margin {
+            return SegmentRelation::Disjoint;
+        }
+        let at_p_end = t.abs() <= t_margin || (t - 1.0).abs() <= t_margin;
+        let at_q_end = u.abs() <= u_margin || (u - 1.0).abs() <= u_margin;
+        if at_p_end && at_q_end {
+            // meeting at a vertex, reported through `duplicate_points` instead
+            return SegmentRelation::Disjoint;
+        }
+        SegmentRelation::Crossing
+    }
+
+    fn duplicate_points(points: &[Point], epsilon: f64) -> Vec<Vec<usize>> {
+        let mut groups: Vec<Vec<usize>> = vec![];
+        let mut group_of: HashMap<usize, usize> = HashMap::new();
+        for (i, representative) in GeometryAnalyzer::representatives(points, epsilon)
+            .into_iter()
+            .enumerate()
+            .filter(|(i, representative)| i != representative)
+        {
+            let group = *group_of.entry(representative).or_insert_with(|| {
+                groups.push(vec![representative]);
+                groups.len() - 1
+            });
+            groups[group].push(i);
+        }
+        groups
+    }
+
+    pub fn detect(chart: &Chart, epsilon: f64) -> IntersectionReport {
+        let segments = GeometryAnalyzer::segments(chart);
+        let duplicate_points = IntersectionDetector::duplicate_points(&chart.points, epsilon);
+        let bounds = match GeometryAnalyzer::bounds(&chart.points) {
+            Some(bounds) if !segments.is_empty() => bounds,
+            _ => {
+                return IntersectionReport {
+                    crossings: vec![],
+                    overlaps: vec![],
+                    duplicate_points,
+                }
+            }
+        };
+
+        let extent = bounds.width().max(bounds.height()).max(epsilon);
+        let average_length = GeometryAnalyzer::total_length(chart) / segments.len() as f64;
+        let cell_size = average_length.max(extent / (2.0 * (segments.len() as f64).sqrt()));
+        let to_cell = |value: f64, origin: f64| ((value - origin) / cell_size).floor() as i64;
+
+        let mut cells: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+        for (i, (start, end)) in segments.iter().enumerate() {
+            let min_x = to_cell(start.x.min(end.x) - epsilon, bounds.min.x);
+            let max_x = to_cell(start.x.max(end.x) + epsilon, bounds.min.x);
+            let min_y = to_cell(start.y.min(end.y) - epsilon, bounds.min.y);
+            let max_y = to_cell(start.y.max(end.y) + epsilon, bounds.min.y);
+            for x in min_x..=max_x {
+                for y in min_y..=max_y {
+                    cells.entry((x, y)).or_default().push(i);
+                }
+            }
+        }
+
+        let mut visited: HashSet<(usize, usize)> = HashSet::new();
+        let mut crossings: Vec<(usize, usize)> = vec![];
+        let mut overlaps: Vec<(usize, usize)> = vec![];
+        for members in cells.values() {
+            for (k, &i) in members.iter().enumerate() {
+                for &j in &members[k + 1..] {
+                    if !visited.insert((i, j)) {
+                        continue;
+                    }
+                    match IntersectionDetector::relation(&segments[i], &segments[j], epsilon) {
+                        SegmentRelation::Crossing => crossings.push((i, j)),
+                        SegmentRelation::Overlap => overlaps.push((i, j)),
+                        SegmentRelation::Disjoint => {}
+                    }
+                }
+            }
+        }
+        crossings.sort();
+        overlaps.sort();
+        IntersectionReport {
+            crossings,
+            overlaps,
+            duplicate_points,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::shape::{BaseChartConfig, ChartKind, Mutation, Order, Randomizer},
+        repository::shapes::kinds::selector::ShapesGeneratorSelector,
+    };
+
+    #[test]
+    fn test_segment_intersection_detection() {
+        let chart = Chart {
+            points: vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 2.0, y: 2.0 },
+                Point { x: 0.0, y: 2.0 },
+                Point { x: 2.0, y: 0.0 },
+                Point { x: 3.0, y: 0.0 },
+                Point { x: 1.0, y: 0.0 },
+            ],
+            orders: vec![
+                Order { link: (0, 1) },
+                Order { link: (1, 2) },
+                Order { link: (2, 3) },
+                Order { link: (3, 4) },
+                Order { link: (4, 5) },
+            ],
+        };
+        let actual = IntersectionDetector::detect(&chart, 1e-9);
+        let expect = IntersectionReport {
+            // (2, 4) is a T-junction at (2, 0)
+            crossings: vec![(0, 2), (2, 4)],
+            overlaps: vec![(3, 4)],
+            duplicate_points: vec![],
+        };
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn test_duplicate_points() {
+        let chart = Chart {
+            points: vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 1.0, y: 1.0 },
+                Point { x: 0.0, y: 0.0 },
+                Point {
+                    x: 1.0 + 1e-12,
+                    y: 1.0,
+                },
+                Point { x: -0.0, y: 0.0 },
+            ],
+            orders: (0..4).map(|i| Order { link: (i, i + 1) }).collect(),
+        };
+        let duplicates = |epsilon| IntersectionDetector::detect(&chart, epsilon).duplicate_points;
+        // zero and tolerances far below the coordinates only find exact duplicates
+        assert_eq!(vec![vec![0, 2, 4]], duplicates(0.0));
+        assert_eq!(vec![vec![0, 2, 4]], duplicates(1e-300));
+        assert_eq!(vec![vec![0, 2, 4], vec![1, 3]], duplicates(1e-9));
+    }
+
+    #[test]
+    fn test_dragon_intersection_detection() {
+        let generate = |angle_amplitude: f64| {
+            ShapesGeneratorSelector::select(&ChartKind::FoldDragon).generate_chart(
+                BaseChartConfig {
+                    kind: ChartKind::FoldDragon,
+                    complexity: 16,
+                    mutation: Some(Mutation {
+                        size: 1.0,
+                        angle: 1.0,
+                    }),
+                    randomizer: Some(Randomizer {
+                        size_amplitude: 0.0,
+                        size_seed: 0,
+                        angle_amplitude,
+                        angle_seed: 1,
+                    }),
+                    parameters: None,
+                },
+            )
+        };
+        // the plain dragon only touches itself at its corners
+        let plain = IntersectionDetector::detect(&generate(0.0), 1e-9);
+        assert!(plain.is_simple());
+        assert!(!plain.duplicate_points.is_empty());
+
+        let mutated = IntersectionDetector::detect(&generate(0.3), 1e-9);
+        assert!(!mutated.crossings.is_empty());
+    }
+}
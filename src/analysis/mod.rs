@@ -0,0 +1,4 @@
+pub mod dimension;
+pub mod geometry;
+pub mod intersection;
+pub mod spatial_index;
@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+
+use super::geometry::{Bounds, GeometryAnalyzer};
+use crate::model::shape::{Chart, Point};
+
+// uniform grid over the segments and points of a chart; every result is an index into `Chart::orders` or `Chart::points`
+pub struct SpatialIndex {
+    segments: Vec<(Point, Point)>,
+    points: Vec<Point>,
+    origin: Point,
+    cell_size: f64,
+    // inclusive range of occupied cells
+    min_cell: (i64, i64),
+    max_cell: (i64, i64),
+    segment_cells: HashMap<(i64, i64), Vec<usize>>,
+    point_cells: HashMap<(i64, i64), Vec<usize>>,
+}
+
+impl SpatialIndex {
+    pub fn build(chart: &Chart) -> Self {
+        let segments = GeometryAnalyzer::segments(chart);
+        let points = chart.points.clone();
+        let origin = match GeometryAnalyzer::bounds(&points) {
+            Some(bounds) => bounds.min,
+            None => Point { x: 0.0, y: 0.0 },
+        };
+        let extent = GeometryAnalyzer::bounds(&points)
+            .map(|bounds| bounds.width().max(bounds.height()))
+            .unwrap_or(0.0);
+        let count = segments.len().max(points.len()).max(1) as f64;
+        let average_length = if segments.is_empty() {
+            0.0
+        } else {
+            GeometryAnalyzer::total_length(chart) / segments.len() as f64
+        };
+        let cell_size = average_length
+            .max(extent / (2.0 * count.sqrt()))
+            .max(f64::EPSILON);
+
+        let mut index = SpatialIndex {
+            segments,
+            points,
+            origin,
+            cell_size,
+            min_cell: (0, 0),
+            max_cell: (0, 0),
+            segment_cells: HashMap::new(),
+            point_cells: HashMap::new(),
+        };
+        for i in 0..index.segments.len() {
+            let (start, end) = index.segments[i];
+            let (min, max) = index.cell_range(&SpatialIndex::segment_bounds(&start, &end));
+            for x in min.0..=max.0 {
+                for y in min.1..=max.1 {
+                    index.segment_cells.entry((x, y)).or_default().push(i);
+                }
+            }
+            index.max_cell = (index.max_cell.0.max(max.0), index.max_cell.1.max(max.1));
+        }
+        for i in 0..index.points.len() {
+            let cell = index.cell(&index.points[i]);
+            index.point_cells.entry(cell).or_default().push(i);
+            index.max_cell = (index.max_cell.0.max(cell.0), index.max_cell.1.max(cell.1));
+        }
+        index
+    }
+
+    fn cell(&self, point: &Point) -> (i64, i64) {
+        (
+            ((point.x - self.origin.x) / self.cell_size).floor() as i64,
+            ((point.y - self.origin.y) / self.cell_size).floor() as i64,
+        )
+    }
+
+    fn cell_range(&self, bounds: &Bounds) -> ((i64, i64), (i64, i64)) {
+        (self.cell(&bounds.min), self.cell(&bounds.max))
+    }
+
+    fn segment_bounds(start: &Point, end: &Point) -> Bounds {
+        Bounds {
+            min: Point {
+                x: start.x.min(end.x),
+                y: start.y.min(end.y),
+            },
+            max: Point {
+                x: start.x.max(end.x),
+                y: start.y.max(end.y),
+            },
+        }
+    }
+
+    pub fn distance_to_segment(point: &Point, start: &Point, end: &Point) -> f64 {
+        let (dx, dy) = (end.x - start.x, end.y - start.y);
+        let squared = dx * dx + dy * dy;
+        let t = if squared == 0.0 {
+            0.0
+        } else {
+            (((point.x - start.x) * dx + (point.y - start.y) * dy) / squared).clamp(0.0, 1.0)
+        };
+        (start.x + t * dx - point.x).hypot(start.y + t * dy - point.y)
+    }
+
+    // visits cells ring by ring around `point` until no closer candidate can remain; a query
+    // outside the grid starts from the nearest occupied row and column, which is no further from any cell
+    fn nearest<F>(
+        &self,
+        point: &Point,
+        cells: &HashMap<(i64, i64), Vec<usize>>,
+        distance: F,
+    ) -> Option<(usize, f64)>
+    where
+        F: Fn(usize) -> f64,
+    {
+        if cells.is_empty() {
+            return None;
+        }
+        let cell = self.cell(point);
+        let center = (
+            cell.0.clamp(self.min_cell.0, self.max_cell.0),
+            cell.1.clamp(self.min_cell.1, self.max_cell.1),
+        );
+        let reach = [
+            center.0 - self.min_cell.0,
+            self.max_cell.0 - center.0,
+            center.1 - self.min_cell.1,
+            self.max_cell.1 - center.1,
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or(0);
+        let mut best: Option<(usize, f64)> = None;
+        for ring in 0..=reach {
+            let ring_cells = (-ring..=ring).flat_map(|dx| {
+                (-ring..=ring)
+                    .filter(move |dy| dx.abs() == ring || dy.abs() == ring)
+                    .map(move |dy| (center.0 + dx, center.1 + dy))
+            });
+            for cell in ring_cells {
+                for &i in cells.get(&cell).into_iter().flatten() {
+                    let d = distance(i);
+                    if best.is_none_or(|(j, best_distance)| {
+                        d < best_distance || (d == best_distance && i < j)
+                    }) {
+                        best = Some((i, d));
+                    }
+                }
+            }
+            // cells past this ring are at least `ring` cells away from the query
+            if let Some((_, best_distance)) = best {
+                if best_distance <= ring as f64 * self.cell_size {
+                    break;
+                }
+            }
+        }
+        best
+    }
+
+    // index into `Chart::orders` and distance of the closest segment
+    pub fn nearest_segment(&self, point: &Point) -> Option<(usize, f64)> {
+        self.nearest(point, &self.segment_cells, |i| {
+            let (start, end) = &self.segments[i];
+            SpatialIndex::distance_to_segment(point, start, end)
+        })
+    }
+
+    // index into `Chart::points` and distance of the closest point
+    pub fn nearest_point(&self, point: &Point) -> Option<(usize, f64)> {
+        self.nearest(point, &self.point_cells, |i| {
+            let other = &self.points[i];
+            (other.x - point.x).hypot(other.y - point.y)
+        })
+    }
+
+    // Liang-Barsky clipping of the segment against the rectangle
+    fn segment_touches(start: &Point, end: &Point, rect: &Bounds) -> bool {
+        let (dx, dy) = (end.x - start.x, end.y - start.y);
+        let mut enter: f64 = 0.0;
+        let mut exit: f64 = 1.0;
+        for (p, q) in [
+            (-dx, start.x - rect.min.x),
+            (dx, rect.max.x - start.x),
+            (-dy, start.y - rect.min.y),
+            (dy, rect.max.y - start.y),
+        ] {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return false;
+                }
+                continue;
+            }
+            let t = q / p;
+            if p < 0.0 {
+                enter = enter.max(t);
+            } else {
+                exit = exit.min(t);
+            }
+            if enter > exit {
+                return false;
+            }
+        }
+        true
+    }
+
+    // indices into `Chart::orders` of segments with any part inside `rect`, ascending
+    pub fn segments_in(&self, rect: &Bounds) -> Vec<usize> {
+        let (min, max) = self.cell_range(rect);
+        let mut found: Vec<usize> = vec![];
+        for x in min.0.max(self.min_cell.0)..=max.0.min(self.max_cell.0) {
+            for y in min.1.max(self.min_cell.1)..=max.1.min(self.max_cell.1) {
+                found.extend(self.segment_cells.get(&(x, y)).into_iter().flatten());
+            }
+        }
+        found.sort();
+        found.dedup();
+        found.retain(|&i| {
+            let (start, end) = &self.segments[i];
+            SpatialIndex::segment_touches(start, end, rect)
+        });
+        found
+    }
+
+    // indices into `Chart::points` of points inside `rect`, ascending
+    pub fn points_in(&self, rect: &Bounds) -> Vec<usize> {
+        let (min, max) = self.cell_range(rect);
+        let mut found: Vec<usize> = vec![];
+        for x in min.0.max(self.min_cell.0)..=max.0.min(self.max_cell.0) {
+            for y in min.1.max(self.min_cell.1)..=max.1.min(self.max_cell.1) {
+                found.extend(self.point_cells.get(&(x, y)).into_iter().flatten());
+            }
+        }
+        found.retain(|&i| {
+            let p = &self.points[i];
+            p.x >= rect.min.x && p.x <= rect.max.x && p.y >= rect.min.y && p.y <= rect.max.y
+        });
+        found.sort();
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::shape::{BaseChartConfig, ChartKind, Mutation, Order, Randomizer},
+        repository::shapes::{
+            generator::randomizer::RandomGenerator, kinds::selector::ShapesGeneratorSelector,
+        },
+    };
+
+    #[test]
+    fn test_small_chart_queries() {
+        let chart = Chart {
+            points: vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 4.0, y: 0.0 },
+                Point { x: 4.0, y: 4.0 },
+            ],
+            orders: vec![Order { link: (0, 1) }, Order { link: (1, 2) }],
+        };
+        let index = SpatialIndex::build(&chart);
+
+        assert_eq!(
+            Some((1, 1.0)),
+            index.nearest_segment(&Point { x: 3.0, y: 3.0 })
+        );
+        assert_eq!(
+            Some((0, 2.0)),
+            index.nearest_segment(&Point { x: 1.0, y: -2.0 })
+        );
+        assert_eq!(
+            Some((2, 1.0)),
+            index.nearest_point(&Point { x: 4.0, y: 5.0 })
+        );
+        let rect = Bounds {
+            min: Point { x: 3.0, y: 1.0 },
+            max: Point { x: 5.0, y: 2.0 },
+        };
+        assert_eq!(vec![1], index.segments_in(&rect));
+        assert_eq!(Vec::<usize>::new(), index.points_in(&rect));
+
+        // far queries start at the edge of the grid instead of walking towards it
+        assert_eq!(
+            Some((0, 1e12)),
+            index.nearest_segment(&Point { x: 2.0, y: -1e12 })
+        );
+        assert_eq!(
+            Some((2, 1e12 - 4.0)),
+            index.nearest_point(&Point { x: 4.0, y: 1e12 })
+        );
+    }
+
+    #[test]
+    fn test_dragon_queries_match_brute_force() {
+        let chart = ShapesGeneratorSelector::select(&ChartKind::FoldDragon).generate_chart(
+            BaseChartConfig {
+                kind: ChartKind::FoldDragon,
+                complexity: 12,
+                mutation: Some(Mutation {
+                    size: 1.0,
+                    angle: 1.0,
+                }),
+                randomizer: Some(Randomizer {
+                    size_amplitude: 0.1,
+                    size_seed: 2,
+                    angle_amplitude: 0.1,
+                    angle_seed: 3,
+                }),
+                parameters: None,
+            },
+        );
+        let index = SpatialIndex::build(&chart);
+        let segments = GeometryAnalyzer::segments(&chart);
+        let mut rng = RandomGenerator::new(7, 4.0);
+
+        for _ in 0..50 {
+            let query = Point {
+                x: rng.generate() - 2.0,
+                y: rng.generate() - 2.0,
+            };
+            let expect = segments
+                .iter()
+                .map(|(start, end)| SpatialIndex::distance_to_segment(&query, start, end))
+                .fold(f64::INFINITY, f64::min);
+            let (_, actual) = index.nearest_segment(&query).unwrap();
+            assert_eq!(expect, actual);
+        }
+    }
+}
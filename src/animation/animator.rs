@@ -0,0 +1,239 @@
+use crate::{
+    model::shape::{AttributedChart, BaseChartConfig, Chart, ChartKind, Mutation, Point},
+    repository::shapes::kinds::selector::ShapesGeneratorSelector,
+};
+
+pub struct Animator;
+
+impl Animator {
+    fn lerp(from: &Point, to: &Point, t: f64) -> Point {
+        Point {
+            x: from.x + (to.x - from.x) * t,
+            y: from.y + (to.y - from.y) * t,
+        }
+    }
+
+    // frame `i` of `frame_count` draws the first (i + 1) / frame_count of the orders
+    pub fn progressive(chart: &Chart, frame_count: usize) -> Vec<Chart> {
+        let order_count = chart.orders.len();
+        (1..=frame_count)
+            .map(|frame| Chart {
+                points: chart.points.clone(),
+                orders: chart.orders[..(order_count * frame).div_ceil(frame_count)].to_vec(),
+            })
+            .collect()
+    }
+
+    // where every point of `next` sits while it is still the `previous` generation
+    fn growth_origins(
+        kind: &ChartKind,
+        previous: &AttributedChart,
+        next: &AttributedChart,
+    ) -> Vec<Point> {
+        let old = &previous.chart.points;
+        let new = &next.chart.points;
+        if old.is_empty() {
+            return new.clone();
+        }
+        if let ChartKind::BinaryTree = kind {
+            // heap order: new branches sprout from their parent node
+            return (0..new.len())
+                .map(|i| {
+                    let mut node = i;
+                    while node >= old.len() {
+                        node /= 2;
+                    }
+                    old[node]
+                })
+                .collect();
+        }
+
+        let depth = previous
+            .point_attributes
+            .iter()
+            .map(|attributes| attributes.depth)
+            .max()
+            .unwrap_or(0);
+        let existing: Vec<usize> = (0..new.len())
+            .filter(|&i| next.point_attributes[i].depth <= depth)
+            .collect();
+        if depth == 0 || existing.len() != old.len() {
+            // no generation structure to follow, so points keep their index
+            return (0..new.len()).map(|i| old[i.min(old.len() - 1)]).collect();
+        }
+
+        // points inserted between two existing ones start evenly spread on the line joining them
+        let mut origins: Vec<Point> = vec![];
+        for (rank, window) in existing.windows(2).enumerate() {
+            let (from, to) = (window[0], window[1]);
+            let gap = (to - from) as f64;
+            for i in from..to {
+                origins.push(Animator::lerp(
+                    &old[rank],
+                    &old[rank + 1],
+                    (i - from) as f64 / gap,
+                ));
+            }
+        }
+        let last = existing[existing.len() - 1];
+        origins.extend((last..new.len()).map(|_| old[old.len() - 1]));
+        let first = existing[0];
+        let mut leading: Vec<Point> = (0..first).map(|_| old[0]).collect();
+        leading.append(&mut origins);
+        leading
+    }
+
+    // morphs complexity k into k + 1 for every k from `config.complexity` up to `target_complexity`
+    pub fn grow(
+        config: &BaseChartConfig,
+        target_complexity: u64,
+        frames_per_level: usize,
+    ) -> Vec<Chart> {
+        let generator = ShapesGeneratorSelector::select(&config.kind);
+        let generate = |complexity: u64| {
+            generator.generate_attributed_chart(BaseChartConfig {
+                complexity,
+                ..*config
+            })
+        };
+        let mut frames: Vec<Chart> = vec![];
+        let mut previous = generate(config.complexity);
+        for complexity in config.complexity + 1..=target_complexity {
+            let next = generate(complexity);
+            let origins = Animator::growth_origins(&config.kind, &previous, &next);
+            for frame in 0..frames_per_level {
+                let t = frame as f64 / frames_per_level as f64;
+                frames.push(Chart {
+                    points: origins
+                        .iter()
+                        .zip(&next.chart.points)
+                        .map(|(from, to)| Animator::lerp(from, to, t))
+                        .collect(),
+                    orders: next.chart.orders.clone(),
+                });
+            }
+            previous = next;
+        }
+        frames.push(previous.chart);
+        frames
+    }
+
+    // regenerates the chart for each step between the two mutations, both ends included
+    pub fn tween_mutation(
+        config: &BaseChartConfig,
+        from: &Mutation,
+        to: &Mutation,
+        frame_count: usize,
+    ) -> Vec<Chart> {
+        let generator = ShapesGeneratorSelector::select(&config.kind);
+        (0..frame_count)
+            .map(|frame| {
+                let t = if frame_count > 1 {
+                    frame as f64 / (frame_count - 1) as f64
+                } else {
+                    0.0
+                };
+                generator.generate_chart(BaseChartConfig {
+                    mutation: Some(Mutation {
+                        size: from.size + (to.size - from.size) * t,
+                        angle: from.angle + (to.angle - from.angle) * t,
+                    }),
+                    ..*config
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::shape::{Order, Randomizer};
+
+    fn config(kind: ChartKind, complexity: u64) -> BaseChartConfig {
+        BaseChartConfig {
+            kind,
+            complexity,
+            mutation: Some(Mutation {
+                size: 1.0,
+                angle: 1.0,
+            }),
+            randomizer: Some(Randomizer {
+                size_amplitude: 0.0,
+                size_seed: 0,
+                angle_amplitude: 0.0,
+                angle_seed: 0,
+            }),
+            parameters: None,
+        }
+    }
+
+    #[test]
+    fn test_progressive_frames() {
+        let chart = Chart {
+            points: vec![Point { x: 0.0, y: 0.0 }; 6],
+            orders: (0..5).map(|i| Order { link: (i, i + 1) }).collect(),
+        };
+        let actual = Animator::progressive(&chart, 3);
+        assert_eq!(
+            vec![2, 4, 5],
+            actual
+                .iter()
+                .map(|c| c.orders.len())
+                .collect::<Vec<usize>>()
+        );
+    }
+
+    #[test]
+    fn test_dragon_growth() {
+        let frames = Animator::grow(&config(ChartKind::FoldDragon, 3), 4, 4);
+        assert_eq!(5, frames.len());
+
+        // the first frame is the complexity 3 dragon with its new points on the old segments
+        let start = ShapesGeneratorSelector::select(&ChartKind::FoldDragon)
+            .generate_chart(config(ChartKind::FoldDragon, 3));
+        assert_eq!(9, frames[0].points.len());
+        assert_eq!(start.points[1], frames[0].points[2]);
+        assert_eq!(
+            Animator::lerp(&start.points[1], &start.points[2], 0.5),
+            frames[0].points[3]
+        );
+        let end = ShapesGeneratorSelector::select(&ChartKind::FoldDragon)
+            .generate_chart(config(ChartKind::FoldDragon, 4));
+        assert_eq!(&end, frames.last().unwrap());
+    }
+
+    #[test]
+    fn test_binary_tree_growth() {
+        let frames = Animator::grow(&config(ChartKind::BinaryTree, 2), 3, 2);
+        let start = ShapesGeneratorSelector::select(&ChartKind::BinaryTree)
+            .generate_chart(config(ChartKind::BinaryTree, 2));
+        assert_eq!(16, frames[0].points.len());
+        assert_eq!(start.points[5], frames[0].points[5]);
+        assert_eq!(start.points[5], frames[0].points[10]);
+        assert_eq!(start.points[5], frames[0].points[11]);
+    }
+
+    #[test]
+    fn test_mutation_tween() {
+        let frames = Animator::tween_mutation(
+            &config(ChartKind::FoldCCurve, 4),
+            &Mutation {
+                size: 1.0,
+                angle: 0.0,
+            },
+            &Mutation {
+                size: 1.0,
+                angle: 1.0,
+            },
+            3,
+        );
+        let flat = ShapesGeneratorSelector::select(&ChartKind::FoldCCurve)
+            .generate_chart(config(ChartKind::FoldCCurve, 4));
+        assert_eq!(3, frames.len());
+        assert_eq!(Point { x: 1.0, y: 0.0 }, frames[0].points[8]);
+        // without folding angle every point stays on the initial segment
+        assert!(frames[0].points.iter().all(|p| p.y.abs() < 1e-12));
+        assert_eq!(flat, frames[2]);
+    }
+}
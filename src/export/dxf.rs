@@ -0,0 +1,186 @@
+use super::strokes::StrokePlanner;
+use crate::model::{
+    shape::{Chart, Point},
+    transform::Transform,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DxfUnits {
+    Unitless,
+    Inches,
+    Millimetres,
+    Centimetres,
+    Metres,
+}
+
+impl DxfUnits {
+    // R12 has no header variable for units, so coordinates are converted from millimetres instead
+    fn per_millimetre(&self) -> f64 {
+        match self {
+            DxfUnits::Unitless | DxfUnits::Millimetres => 1.0,
+            DxfUnits::Inches => 1.0 / 25.4,
+            DxfUnits::Centimetres => 0.1,
+            DxfUnits::Metres => 0.001,
+        }
+    }
+}
+
+pub struct DxfConfig {
+    pub units: DxfUnits,
+    // millimetres per chart unit, or drawing units per chart unit when unitless
+    pub scale: f64,
+    // points closer than this, in chart units, are joined into one polyline
+    pub merge_tolerance: f64,
+}
+
+pub struct DxfWriter;
+
+impl DxfWriter {
+    fn pair(output: &mut String, code: u64, value: &str) {
+        *output += &format!("{}\n{}\n", code, value);
+    }
+
+    fn coordinates(output: &mut String, point: &Point, x_code: u64) {
+        DxfWriter::pair(output, x_code, &format!("{:.6}", point.x));
+        DxfWriter::pair(output, x_code + 10, &format!("{:.6}", point.y));
+    }
+
+    // a chained stroke ending on its start vertex is written as a closed polyline, so CAM treats it as an outline
+    fn entity(output: &mut String, stroke: &[Point]) {
+        let closed = stroke.len() > 3 && stroke[0] == stroke[stroke.len() - 1];
+        if stroke.len() == 2 {
+            DxfWriter::pair(output, 0, "LINE");
+            DxfWriter::pair(output, 8, "0");
+            DxfWriter::coordinates(output, &stroke[0], 10);
+            DxfWriter::coordinates(output, &stroke[1], 11);
+            return;
+        }
+        let vertices = if closed {
+            &stroke[..stroke.len() - 1]
+        } else {
+            stroke
+        };
+        DxfWriter::pair(output, 0, "POLYLINE");
+        DxfWriter::pair(output, 8, "0");
+        DxfWriter::pair(output, 66, "1");
+        DxfWriter::coordinates(output, &Point { x: 0.0, y: 0.0 }, 10);
+        DxfWriter::pair(output, 70, if closed { "1" } else { "0" });
+        for vertex in vertices {
+            DxfWriter::pair(output, 0, "VERTEX");
+            DxfWriter::pair(output, 8, "0");
+            DxfWriter::coordinates(output, vertex, 10);
+        }
+        DxfWriter::pair(output, 0, "SEQEND");
+        DxfWriter::pair(output, 8, "0");
+    }
+
+    // R12 ASCII sections, which every CAD and CAM tool reads
+    pub fn write(chart: &Chart, config: &DxfConfig) -> String {
+        let mut output = String::new();
+        DxfWriter::pair(&mut output, 0, "SECTION");
+        DxfWriter::pair(&mut output, 2, "HEADER");
+        DxfWriter::pair(&mut output, 9, "$ACADVER");
+        DxfWriter::pair(&mut output, 1, "AC1009");
+        DxfWriter::pair(&mut output, 0, "ENDSEC");
+        DxfWriter::pair(&mut output, 0, "SECTION");
+        DxfWriter::pair(&mut output, 2, "ENTITIES");
+        let scale = config.scale * config.units.per_millimetre();
+        let transform = Transform::scale(scale, scale);
+        for stroke in StrokePlanner::plan(chart, &transform, config.merge_tolerance) {
+            DxfWriter::entity(&mut output, &stroke);
+        }
+        DxfWriter::pair(&mut output, 0, "ENDSEC");
+        DxfWriter::pair(&mut output, 0, "EOF");
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::shape::{BaseChartConfig, ChartKind, Mutation, Order, Randomizer},
+        repository::shapes::kinds::selector::ShapesGeneratorSelector,
+    };
+
+    fn config() -> DxfConfig {
+        DxfConfig {
+            units: DxfUnits::Millimetres,
+            scale: 10.0,
+            merge_tolerance: 1e-9,
+        }
+    }
+
+    #[test]
+    fn test_line_entities() {
+        let chart = Chart {
+            points: vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 1.0, y: 0.0 },
+                Point { x: 1.0, y: 2.0 },
+                Point { x: 5.0, y: 5.0 },
+            ],
+            orders: vec![Order { link: (0, 1) }, Order { link: (1, 2) }],
+        };
+        let actual = DxfWriter::write(&chart, &config());
+        assert!(actual.starts_with("0\nSECTION\n2\nHEADER\n9\n$ACADVER\n1\nAC1009\n0\nENDSEC\n"));
+        assert!(actual.contains(concat!(
+            "0\nPOLYLINE\n8\n0\n66\n1\n10\n0.000000\n20\n0.000000\n70\n0\n",
+            "0\nVERTEX\n8\n0\n10\n0.000000\n20\n0.000000\n",
+            "0\nVERTEX\n8\n0\n10\n10.000000\n20\n0.000000\n",
+            "0\nVERTEX\n8\n0\n10\n10.000000\n20\n20.000000\n",
+            "0\nSEQEND\n8\n0\n"
+        )));
+        assert!(actual.ends_with("0\nENDSEC\n0\nEOF\n"));
+
+        let single = Chart {
+            points: chart.points.clone(),
+            orders: vec![Order { link: (2, 3) }],
+        };
+        assert!(DxfWriter::write(&single, &config()).contains(concat!(
+            "0\nLINE\n8\n0\n",
+            "10\n10.000000\n20\n20.000000\n",
+            "11\n50.000000\n21\n50.000000\n"
+        )));
+
+        // the same millimetres written in centimetres
+        let centimetres = DxfWriter::write(
+            &single,
+            &DxfConfig {
+                units: DxfUnits::Centimetres,
+                ..config()
+            },
+        );
+        assert!(centimetres.contains("11\n5.000000\n21\n5.000000\n"));
+    }
+
+    #[test]
+    fn test_closed_outlines() {
+        for (kind, complexity) in [
+            (ChartKind::Star, 7),
+            (ChartKind::KochTriangleOuter, 3),
+            (ChartKind::KochTriangleInner, 3),
+        ] {
+            let chart = ShapesGeneratorSelector::select(&kind).generate_chart(BaseChartConfig {
+                kind,
+                complexity,
+                mutation: Some(Mutation {
+                    size: 1.0,
+                    angle: 1.0,
+                }),
+                randomizer: Some(Randomizer {
+                    size_amplitude: 0.0,
+                    size_seed: 0,
+                    angle_amplitude: 0.0,
+                    angle_seed: 0,
+                }),
+                parameters: None,
+            });
+            let actual = DxfWriter::write(&chart, &config());
+            assert_eq!(1, actual.matches("0\nPOLYLINE\n").count());
+            assert_eq!(1, actual.matches("0\nSEQEND\n").count());
+            assert!(actual.contains("70\n1\n0\nVERTEX\n"));
+            assert!(!actual.contains("0\nLINE\n"));
+        }
+    }
+}
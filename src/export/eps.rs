@@ -0,0 +1,83 @@
+use super::{
+    page::{Page, YAxis},
+    pdf::VectorConfig,
+    strokes::StrokePlanner,
+};
+use crate::model::shape::Chart;
+
+pub struct EpsWriter;
+
+impl EpsWriter {
+    // EPS holds a single page, so only the first page of the layout is written
+    pub fn write(charts: &[Chart], config: &VectorConfig) -> String {
+        let Page { size, .. } = &config.page;
+        let mut output = format!(
+            "%!PS-Adobe-3.0 EPSF-3.0\n%%BoundingBox: 0 0 {} {}\n%%HiResBoundingBox: 0 0 {:.3} {:.3}\n%%EndComments\n",
+            size.width.ceil(),
+            size.height.ceil(),
+            size.width,
+            size.height
+        );
+        output += &format!(
+            "1 setlinecap 1 setlinejoin {:.3} setlinewidth 0 setgray\n",
+            config.stroke_width
+        );
+        let pages = config.page.layout(charts, &config.layout, YAxis::Up);
+        for (index, transform) in pages.first().into_iter().flatten() {
+            let chart = &charts[*index];
+            for run in StrokePlanner::runs(chart) {
+                let points: Vec<_> = run
+                    .iter()
+                    .map(|&i| transform.apply(&chart.points[i]))
+                    .collect();
+                output += &format!("newpath {:.3} {:.3} moveto", points[0].x, points[0].y);
+                for p in &points[1..] {
+                    output += &format!(" {:.3} {:.3} lineto", p.x, p.y);
+                }
+                output += " stroke\n";
+            }
+        }
+        output += "showpage\n%%EOF\n";
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        export::page::GalleryLayout,
+        model::shape::{Order, Point, Size},
+    };
+
+    #[test]
+    fn test_eps_output() {
+        let chart = Chart {
+            points: vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 1.0, y: 0.0 },
+                Point { x: 1.0, y: 1.0 },
+            ],
+            orders: vec![Order { link: (0, 1) }, Order { link: (1, 2) }],
+        };
+        let actual = EpsWriter::write(
+            &[chart],
+            &VectorConfig {
+                page: Page {
+                    size: Size {
+                        width: 100.0,
+                        height: 100.0,
+                    },
+                    margin: 0.0,
+                },
+                stroke_width: 0.5,
+                layout: GalleryLayout::PagePerChart,
+            },
+        );
+        assert!(actual.starts_with("%!PS-Adobe-3.0 EPSF-3.0\n%%BoundingBox: 0 0 100 100\n"));
+        assert!(actual.contains(
+            "newpath 0.000 0.000 moveto 100.000 0.000 lineto 100.000 100.000 lineto stroke\n"
+        ));
+        assert!(actual.ends_with("showpage\n%%EOF\n"));
+    }
+}
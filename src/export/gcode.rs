@@ -0,0 +1,79 @@
+use super::{
+    page::YAxis,
+    strokes::{PlotterConfig, StrokePlanner},
+};
+use crate::{analysis::geometry::GeometryAnalyzer, model::shape::Chart};
+
+pub struct GcodeConfig {
+    pub plotter: PlotterConfig,
+    // millimetres per minute
+    pub feed_rate: f64,
+    pub pen_up_z: f64,
+    pub pen_down_z: f64,
+}
+
+pub struct GcodeWriter;
+
+impl GcodeWriter {
+    pub fn write(chart: &Chart, config: &GcodeConfig) -> String {
+        let mut lines: Vec<String> = vec![
+            "G21".to_string(),
+            "G90".to_string(),
+            format!("G0 Z{:.3}", config.pen_up_z),
+        ];
+        if let Some(bounds) = GeometryAnalyzer::bounds(&chart.points) {
+            let transform = config.plotter.page.fit(&bounds, YAxis::Up);
+            let strokes = StrokePlanner::plan(chart, &transform, config.plotter.merge_tolerance);
+            for stroke in strokes {
+                lines.push(format!("G0 X{:.3} Y{:.3}", stroke[0].x, stroke[0].y));
+                lines.push(format!(
+                    "G1 Z{:.3} F{:.0}",
+                    config.pen_down_z, config.feed_rate
+                ));
+                for p in &stroke[1..] {
+                    lines.push(format!("G1 X{:.3} Y{:.3}", p.x, p.y));
+                }
+                lines.push(format!("G0 Z{:.3}", config.pen_up_z));
+            }
+        }
+        lines.push("G0 X0 Y0".to_string());
+        lines.join("\n") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        export::page::Page,
+        model::shape::{Order, Point, Size},
+    };
+
+    #[test]
+    fn test_gcode_output() {
+        let chart = Chart {
+            points: vec![Point { x: 0.0, y: 0.0 }, Point { x: 2.0, y: 1.0 }],
+            orders: vec![Order { link: (0, 1) }],
+        };
+        let actual = GcodeWriter::write(
+            &chart,
+            &GcodeConfig {
+                plotter: PlotterConfig {
+                    page: Page {
+                        size: Size {
+                            width: 60.0,
+                            height: 30.0,
+                        },
+                        margin: 5.0,
+                    },
+                    merge_tolerance: 1e-9,
+                },
+                feed_rate: 1500.0,
+                pen_up_z: 5.0,
+                pen_down_z: 0.0,
+            },
+        );
+        let expect = "G21\nG90\nG0 Z5.000\nG0 X10.000 Y5.000\nG1 Z0.000 F1500\nG1 X50.000 Y25.000\nG0 Z5.000\nG0 X0 Y0\n";
+        assert_eq!(expect, actual);
+    }
+}
@@ -0,0 +1,356 @@
+use std::collections::HashMap;
+
+use super::raster::Image;
+use crate::style::palette::Color;
+
+// a colour with the number of pixels using it
+type Weighted = ((u8, u8, u8), u64);
+
+pub struct GifConfig {
+    // per frame, in hundredths of a second
+    pub delay: u16,
+    // 0 repeats forever
+    pub loop_count: u16,
+    // colours of the shared palette, 2 to 256
+    pub palette_size: usize,
+}
+
+pub struct GifEncoder;
+
+impl GifEncoder {
+    // median cut over the distinct colours of every frame, weighted by how often they appear
+    pub fn quantize(frames: &[Image], palette_size: usize) -> Vec<Color> {
+        let mut histogram: HashMap<(u8, u8, u8), u64> = HashMap::new();
+        for frame in frames {
+            for pixel in &frame.pixels {
+                *histogram.entry((pixel.r, pixel.g, pixel.b)).or_default() += 1;
+            }
+        }
+        let mut boxes: Vec<Vec<Weighted>> = vec![histogram.into_iter().collect()];
+        boxes[0].sort();
+
+        let channel = |color: &(u8, u8, u8), axis: usize| match axis {
+            0 => color.0,
+            1 => color.1,
+            _ => color.2,
+        };
+        let range = |colors: &[Weighted], axis: usize| {
+            let values = colors.iter().map(|(color, _)| channel(color, axis));
+            values.clone().max().unwrap_or(0) - values.min().unwrap_or(0)
+        };
+        while boxes.len() < palette_size.clamp(2, 256) {
+            let widest = boxes
+                .iter()
+                .enumerate()
+                .filter(|(_, colors)| colors.len() > 1)
+                .max_by_key(|(_, colors)| (0..3).map(|axis| range(colors, axis)).max());
+            let index = match widest {
+                Some((index, _)) => index,
+                None => break,
+            };
+            let mut colors = boxes.swap_remove(index);
+            let axis = (0..3).max_by_key(|&axis| range(&colors, axis)).unwrap_or(0);
+            colors.sort_by_key(|(color, _)| channel(color, axis));
+            let total: u64 = colors.iter().map(|(_, count)| count).sum();
+            let mut seen = 0;
+            let split = colors
+                .iter()
+                .position(|(_, count)| {
+                    seen += count;
+                    seen * 2 >= total
+                })
+                .unwrap_or(0)
+                .clamp(0, colors.len() - 2)
+                + 1;
+            let upper = colors.split_off(split);
+            boxes.push(colors);
+            boxes.push(upper);
+        }
+
+        boxes
+            .iter()
+            .filter(|colors| !colors.is_empty())
+            .map(|colors| {
+                let total: u64 = colors.iter().map(|(_, count)| count).sum();
+                let average = |axis: usize| {
+                    let sum: u64 = colors
+                        .iter()
+                        .map(|(color, count)| channel(color, axis) as u64 * count)
+                        .sum();
+                    ((sum as f64) / (total as f64)).round() as u8
+                };
+                Color::rgb(average(0), average(1), average(2))
+            })
+            .collect()
+    }
+
+    fn nearest(palette: &[Color], color: &Color) -> u8 {
+        let distance = |other: &Color| {
+            let dr = color.r as i32 - other.r as i32;
+            let dg = color.g as i32 - other.g as i32;
+            let db = color.b as i32 - other.b as i32;
+            dr * dr + dg * dg + db * db
+        };
+        (0..palette.len())
+            .min_by_key(|&i| distance(&palette[i]))
+            .unwrap_or(0) as u8
+    }
+
+    // variable-length LZW codes packed least significant bit first, as GIF expects
+    pub fn compress(indices: &[u8], minimum_code_size: u8) -> Vec<u8> {
+        let clear = 1_u16 << minimum_code_size;
+        let mut writer = CodeWriter {
+            output: vec![],
+            buffer: 0,
+            buffered: 0,
+            code_size: minimum_code_size as u32 + 1,
+            minimum_code_size: minimum_code_size as u32,
+        };
+        let mut next = clear + 2;
+        let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+
+        writer.write(clear, next, false);
+        let mut iter = indices.iter();
+        if let Some(&first) = iter.next() {
+            let mut prefix = first as u16;
+            for &index in iter {
+                if let Some(&code) = table.get(&(prefix, index)) {
+                    prefix = code;
+                    continue;
+                }
+                writer.write(prefix, next, false);
+                if next < 4096 {
+                    table.insert((prefix, index), next);
+                    next += 1;
+                } else {
+                    table.clear();
+                    next = clear + 2;
+                    writer.write(clear, next, true);
+                }
+                prefix = index as u16;
+            }
+            writer.write(prefix, next, false);
+        }
+        writer.write(clear + 1, next, false);
+        writer.finish()
+    }
+
+    fn sub_blocks(data: &[u8], output: &mut Vec<u8>) {
+        for chunk in data.chunks(255) {
+            output.push(chunk.len() as u8);
+            output.extend_from_slice(chunk);
+        }
+        output.push(0);
+    }
+
+    // all frames share one global palette and the size of the first frame
+    pub fn encode(frames: &[Image], config: &GifConfig) -> Vec<u8> {
+        let palette = GifEncoder::quantize(frames, config.palette_size);
+        let bits = (1..=8)
+            .find(|&bits| 1_usize << bits >= palette.len())
+            .unwrap_or(8);
+        let (width, height) = frames
+            .first()
+            .map(|frame| (frame.width as u16, frame.height as u16))
+            .unwrap_or((0, 0));
+
+        let mut output: Vec<u8> = b"GIF89a".to_vec();
+        output.extend_from_slice(&width.to_le_bytes());
+        output.extend_from_slice(&height.to_le_bytes());
+        output.extend_from_slice(&[0x80 | ((bits - 1) << 4) | (bits - 1), 0, 0]);
+        for i in 0..1_usize << bits {
+            let color = palette.get(i).copied().unwrap_or(Color::rgb(0, 0, 0));
+            output.extend_from_slice(&[color.r, color.g, color.b]);
+        }
+
+        output.extend_from_slice(&[0x21, 0xff, 0x0b]);
+        output.extend_from_slice(b"NETSCAPE2.0");
+        output.extend_from_slice(&[0x03, 0x01]);
+        output.extend_from_slice(&config.loop_count.to_le_bytes());
+        output.push(0);
+
+        let minimum_code_size = bits.max(2);
+        let mut lookup: HashMap<(u8, u8, u8), u8> = HashMap::new();
+        for frame in frames {
+            output.extend_from_slice(&[0x21, 0xf9, 0x04, 0x04]);
+            output.extend_from_slice(&config.delay.to_le_bytes());
+            output.extend_from_slice(&[0, 0]);
+
+            output.extend_from_slice(&[0x2c, 0, 0, 0, 0]);
+            output.extend_from_slice(&(frame.width as u16).to_le_bytes());
+            output.extend_from_slice(&(frame.height as u16).to_le_bytes());
+            output.push(0);
+
+            let indices: Vec<u8> = frame
+                .pixels
+                .iter()
+                .map(|pixel| {
+                    *lookup
+                        .entry((pixel.r, pixel.g, pixel.b))
+                        .or_insert_with(|| GifEncoder::nearest(&palette, pixel))
+                })
+                .collect();
+            output.push(minimum_code_size);
+            GifEncoder::sub_blocks(
+                &GifEncoder::compress(&indices, minimum_code_size),
+                &mut output,
+            );
+        }
+        output.push(0x3b);
+        output
+    }
+}
+
+struct CodeWriter {
+    output: Vec<u8>,
+    buffer: u32,
+    buffered: u32,
+    code_size: u32,
+    minimum_code_size: u32,
+}
+
+impl CodeWriter {
+    // widens the codes once `next` no longer fits, and narrows them again after a clear code
+    fn write(&mut self, code: u16, next: u16, reset: bool) {
+        self.buffer |= (code as u32) << self.buffered;
+        self.buffered += self.code_size;
+        while self.buffered >= 8 {
+            self.output.push((self.buffer & 0xff) as u8);
+            self.buffer >>= 8;
+            self.buffered -= 8;
+        }
+        if reset {
+            self.code_size = self.minimum_code_size + 1;
+        } else if self.code_size < 12 && next as u32 > (1 << self.code_size) - 1 {
+            self.code_size += 1;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.buffered > 0 {
+            self.output.push((self.buffer & 0xff) as u8);
+        }
+        self.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // reference decoder following the GIF89a specification
+    fn decompress(data: &[u8], minimum_code_size: u8) -> Vec<u8> {
+        let clear = 1_u16 << minimum_code_size;
+        let mut table: Vec<Vec<u8>> = vec![];
+        let mut code_size = minimum_code_size as u32 + 1;
+        let mut output: Vec<u8> = vec![];
+        let mut previous: Option<u16> = None;
+        let (mut buffer, mut buffered, mut position) = (0_u32, 0_u32, 0);
+        loop {
+            while buffered < code_size {
+                buffer |= (data[position] as u32) << buffered;
+                buffered += 8;
+                position += 1;
+            }
+            let code = (buffer & ((1 << code_size) - 1)) as u16;
+            buffer >>= code_size;
+            buffered -= code_size;
+            if code == clear {
+                table = (0..clear + 2).map(|i| vec![i as u8]).collect();
+                code_size = minimum_code_size as u32 + 1;
+                previous = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return output;
+            }
+            let entry = match previous {
+                None => table[code as usize].clone(),
+                Some(previous) => {
+                    let entry = if (code as usize) < table.len() {
+                        table[code as usize].clone()
+                    } else {
+                        let mut entry = table[previous as usize].clone();
+                        entry.push(entry[0]);
+                        entry
+                    };
+                    let mut added = table[previous as usize].clone();
+                    added.push(entry[0]);
+                    table.push(added);
+                    if table.len() == 1 << code_size && code_size < 12 {
+                        code_size += 1;
+                    }
+                    entry
+                }
+            };
+            output.extend_from_slice(&entry);
+            previous = Some(code);
+        }
+    }
+
+    #[test]
+    fn test_lzw_round_trip() {
+        let mut state: u32 = 1;
+        let indices: Vec<u8> = (0..20000)
+            .map(|i| {
+                state = state.wrapping_mul(1103515245).wrapping_add(12345);
+                if i % 7 < 4 {
+                    (i / 100 % 4) as u8
+                } else {
+                    (state >> 16) as u8 % 16
+                }
+            })
+            .collect();
+        let compressed = GifEncoder::compress(&indices, 4);
+        assert_eq!(indices, decompress(&compressed, 4));
+        assert!(compressed.len() < indices.len());
+    }
+
+    #[test]
+    fn test_palette_quantization() {
+        let image = Image {
+            width: 4,
+            height: 1,
+            pixels: vec![
+                Color::rgb(0, 0, 0),
+                Color::rgb(10, 0, 0),
+                Color::rgb(250, 250, 250),
+                Color::rgb(240, 250, 250),
+            ],
+        };
+        let palette = GifEncoder::quantize(&[image], 2);
+        assert_eq!(2, palette.len());
+        assert!(palette.contains(&Color::rgb(5, 0, 0)));
+        assert!(palette.contains(&Color::rgb(245, 250, 250)));
+    }
+
+    #[test]
+    fn test_animation_encoding() {
+        let frame = |color: Color| Image {
+            width: 3,
+            height: 2,
+            pixels: vec![color; 6],
+        };
+        let frames = vec![frame(Color::rgb(255, 0, 0)), frame(Color::rgb(0, 0, 255))];
+        let actual = GifEncoder::encode(
+            &frames,
+            &GifConfig {
+                delay: 5,
+                loop_count: 0,
+                palette_size: 16,
+            },
+        );
+        assert_eq!(b"GIF89a", &actual[..6]);
+        assert_eq!([3, 0, 2, 0], actual[6..10]);
+        assert_eq!(0x3b, *actual.last().unwrap());
+        // one graphic control block per frame, carrying the delay
+        let controls: Vec<usize> = actual
+            .windows(4)
+            .enumerate()
+            .filter(|(_, w)| *w == [0x21, 0xf9, 0x04, 0x04])
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(2, controls.len());
+        assert_eq!([5, 0], actual[controls[0] + 4..controls[0] + 6]);
+    }
+}
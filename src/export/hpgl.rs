@@ -0,0 +1,74 @@
+use super::{
+    page::YAxis,
+    strokes::{PlotterConfig, StrokePlanner},
+};
+use crate::{analysis::geometry::GeometryAnalyzer, model::shape::Chart};
+
+// plotter units per millimetre
+const UNITS_PER_MILLIMETRE: f64 = 40.0;
+
+pub struct HpglWriter;
+
+impl HpglWriter {
+    pub fn write(chart: &Chart, config: &PlotterConfig) -> String {
+        let mut output = String::from("IN;SP1;");
+        if let Some(bounds) = GeometryAnalyzer::bounds(&chart.points) {
+            let transform = config.page.fit(&bounds, YAxis::Up);
+            let unit = |value: f64| (value * UNITS_PER_MILLIMETRE).round() as i64;
+            for stroke in StrokePlanner::plan(chart, &transform, config.merge_tolerance) {
+                output += &format!("PU{},{};", unit(stroke[0].x), unit(stroke[0].y));
+                let path: Vec<String> = stroke[1..]
+                    .iter()
+                    .map(|p| format!("{},{}", unit(p.x), unit(p.y)))
+                    .collect();
+                output += &format!("PD{};", path.join(","));
+            }
+        }
+        output += "PU;SP0;\n";
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        export::page::Page,
+        model::shape::{Order, Point, Size},
+    };
+
+    #[test]
+    fn test_hpgl_output() {
+        let chart = Chart {
+            points: vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 1.0, y: 0.0 },
+                Point { x: 1.0, y: 1.0 },
+                Point { x: 3.0, y: 1.0 },
+                Point { x: 3.0, y: 0.0 },
+            ],
+            orders: vec![
+                Order { link: (0, 1) },
+                Order { link: (1, 2) },
+                Order { link: (3, 4) },
+            ],
+        };
+        let actual = HpglWriter::write(
+            &chart,
+            &PlotterConfig {
+                page: Page {
+                    size: Size {
+                        width: 50.0,
+                        height: 30.0,
+                    },
+                    margin: 10.0,
+                },
+                merge_tolerance: 1e-9,
+            },
+        );
+        assert_eq!(
+            "IN;SP1;PU400,400;PD800,400,800,800;PU1600,800;PD1600,400;PU;SP0;\n",
+            actual
+        );
+    }
+}
@@ -0,0 +1,11 @@
+pub mod dxf;
+pub mod eps;
+pub mod gcode;
+pub mod gif;
+pub mod hpgl;
+pub mod obj;
+pub mod page;
+pub mod pdf;
+pub mod raster;
+pub mod stl;
+pub mod strokes;
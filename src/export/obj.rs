@@ -0,0 +1,51 @@
+use crate::model::mesh::Mesh;
+
+pub struct ObjWriter;
+
+impl ObjWriter {
+    // Wavefront faces count vertices from one
+    pub fn write(mesh: &Mesh) -> String {
+        let mut output = String::new();
+        for v in &mesh.vertices {
+            output += &format!("v {} {} {}\n", v.x, v.y, v.z);
+        }
+        for [a, b, c] in &mesh.triangles {
+            output += &format!("f {} {} {}\n", a + 1, b + 1, c + 1);
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::shape::Point3;
+
+    #[test]
+    fn test_obj_output() {
+        let mesh = Mesh {
+            vertices: vec![
+                Point3 {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+                Point3 {
+                    x: 1.5,
+                    y: 0.0,
+                    z: 0.0,
+                },
+                Point3 {
+                    x: 0.0,
+                    y: 1.0,
+                    z: 2.0,
+                },
+            ],
+            triangles: vec![[0, 1, 2]],
+        };
+        assert_eq!(
+            "v 0 0 0\nv 1.5 0 0\nv 0 1 2\nf 1 2 3\n",
+            ObjWriter::write(&mesh)
+        );
+    }
+}
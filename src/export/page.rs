@@ -0,0 +1,178 @@
+use crate::{
+    analysis::geometry::{Bounds, GeometryAnalyzer},
+    model::{
+        shape::{Chart, Size},
+        transform::Transform,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum YAxis {
+    // mathematical orientation, as in PDF, EPS, DXF and plotter coordinates
+    Up,
+    // screen orientation, as in raster images
+    Down,
+}
+
+pub enum GalleryLayout {
+    PagePerChart,
+    // every chart on one page, filling rows of `columns` cells
+    Grid { columns: usize },
+}
+
+// drawing area in the exporter's unit (pixels, points, millimetres) with an equal margin on every side
+pub struct Page {
+    pub size: Size,
+    pub margin: f64,
+}
+
+impl Page {
+    // bounds shared by every frame, so that an animation does not jump around
+    pub fn bounds(charts: &[Chart]) -> Option<Bounds> {
+        charts
+            .iter()
+            .filter_map(|chart| GeometryAnalyzer::bounds(&chart.points))
+            .reduce(|a, b| a.union(&b))
+    }
+
+    // fits `bounds` into the page inside its margins, preserving the aspect ratio
+    pub fn fit(&self, bounds: &Bounds, y_axis: YAxis) -> Transform {
+        let content = Size {
+            width: (self.size.width - 2.0 * self.margin).max(0.0),
+            height: (self.size.height - 2.0 * self.margin).max(0.0),
+        };
+        let fit = GeometryAnalyzer::fit_transform(bounds, &content)
+            .then(&Transform::translate(self.margin, self.margin));
+        match y_axis {
+            YAxis::Up => fit,
+            YAxis::Down => fit
+                .then(&Transform::scale(1.0, -1.0))
+                .then(&Transform::translate(0.0, self.size.height)),
+        }
+    }
+
+    // per page, the charts drawn on it with the transform placing each one
+    pub fn layout(
+        &self,
+        charts: &[Chart],
+        layout: &GalleryLayout,
+        y_axis: YAxis,
+    ) -> Vec<Vec<(usize, Transform)>> {
+        let bounds: Vec<Option<Bounds>> = charts
+            .iter()
+            .map(|chart| GeometryAnalyzer::bounds(&chart.points))
+            .collect();
+        match layout {
+            GalleryLayout::PagePerChart => bounds
+                .iter()
+                .enumerate()
+                .map(|(i, bounds)| {
+                    bounds
+                        .iter()
+                        .map(|bounds| (i, self.fit(bounds, y_axis)))
+                        .collect()
+                })
+                .collect(),
+            GalleryLayout::Grid { columns } => {
+                let columns = (*columns).max(1);
+                let rows = charts.len().div_ceil(columns).max(1);
+                let cell = Page {
+                    size: Size {
+                        width: (self.size.width - 2.0 * self.margin) / columns as f64,
+                        height: (self.size.height - 2.0 * self.margin) / rows as f64,
+                    },
+                    margin: self.margin / 2.0,
+                };
+                let cells = bounds
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, bounds)| {
+                        let (row, column) = (i / columns, i % columns);
+                        let row_from_bottom = match y_axis {
+                            YAxis::Up => rows - 1 - row,
+                            YAxis::Down => row,
+                        };
+                        let offset = Transform::translate(
+                            self.margin + column as f64 * cell.size.width,
+                            self.margin + row_from_bottom as f64 * cell.size.height,
+                        );
+                        bounds
+                            .as_ref()
+                            .map(|bounds| (i, cell.fit(bounds, y_axis).then(&offset)))
+                    })
+                    .collect();
+                vec![cells]
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::shape::Point;
+
+    #[test]
+    fn test_page_fitting() {
+        let page = Page {
+            size: Size {
+                width: 120.0,
+                height: 70.0,
+            },
+            margin: 10.0,
+        };
+        let bounds = Bounds {
+            min: Point { x: -1.0, y: 0.0 },
+            max: Point { x: 1.0, y: 1.0 },
+        };
+        let up = page.fit(&bounds, YAxis::Up);
+        assert_eq!(Point { x: 10.0, y: 10.0 }, up.apply(&bounds.min));
+        assert_eq!(Point { x: 110.0, y: 60.0 }, up.apply(&bounds.max));
+        let down = page.fit(&bounds, YAxis::Down);
+        assert_eq!(Point { x: 10.0, y: 60.0 }, down.apply(&bounds.min));
+        assert_eq!(Point { x: 110.0, y: 10.0 }, down.apply(&bounds.max));
+    }
+
+    #[test]
+    fn test_grid_layout() {
+        let page = Page {
+            size: Size {
+                width: 220.0,
+                height: 220.0,
+            },
+            margin: 10.0,
+        };
+        let chart = Chart {
+            points: vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }],
+            orders: vec![],
+        };
+        let charts = vec![
+            Chart {
+                points: chart.points.clone(),
+                orders: vec![],
+            },
+            Chart {
+                points: chart.points.clone(),
+                orders: vec![],
+            },
+            chart,
+        ];
+        let pages = page.layout(&charts, &GalleryLayout::Grid { columns: 2 }, YAxis::Up);
+        assert_eq!(1, pages.len());
+        let origins: Vec<Point> = pages[0]
+            .iter()
+            .map(|(_, transform)| transform.apply(&Point { x: 0.0, y: 0.0 }))
+            .collect();
+        // the first row is at the top of the page
+        assert_eq!(
+            vec![
+                Point { x: 15.0, y: 115.0 },
+                Point { x: 115.0, y: 115.0 },
+                Point { x: 15.0, y: 15.0 },
+            ],
+            origins
+        );
+        let single = page.layout(&charts, &GalleryLayout::PagePerChart, YAxis::Up);
+        assert_eq!(3, single.len());
+    }
+}
@@ -0,0 +1,166 @@
+use super::{
+    page::{GalleryLayout, Page, YAxis},
+    strokes::StrokePlanner,
+};
+use crate::model::{shape::Chart, transform::Transform};
+
+pub struct VectorConfig {
+    // in points, 1/72 inch
+    pub page: Page,
+    pub stroke_width: f64,
+    pub layout: GalleryLayout,
+}
+
+pub struct PdfWriter;
+
+impl PdfWriter {
+    fn content(charts: &[Chart], placements: &[(usize, Transform)], stroke_width: f64) -> String {
+        let mut content = format!("1 J 1 j {:.3} w 0 G\n", stroke_width);
+        for (index, transform) in placements {
+            let chart = &charts[*index];
+            for run in StrokePlanner::runs(chart) {
+                let points: Vec<_> = run
+                    .iter()
+                    .map(|&i| transform.apply(&chart.points[i]))
+                    .collect();
+                content += &format!("{:.3} {:.3} m", points[0].x, points[0].y);
+                for p in &points[1..] {
+                    content += &format!(" {:.3} {:.3} l", p.x, p.y);
+                }
+                content += " S\n";
+            }
+        }
+        content
+    }
+
+    // uncompressed PDF 1.4, one content stream per page
+    pub fn write(charts: &[Chart], config: &VectorConfig) -> Vec<u8> {
+        let pages = config.page.layout(charts, &config.layout, YAxis::Up);
+        let page_count = pages.len();
+        // 1: catalog, 2: page tree, then a page and its content stream per page
+        let mut objects: Vec<String> = vec![
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                (0..page_count)
+                    .map(|i| format!("{} 0 R", 3 + 2 * i))
+                    .collect::<Vec<String>>()
+                    .join(" "),
+                page_count
+            ),
+        ];
+        for (i, placements) in pages.iter().enumerate() {
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {:.3} {:.3}] /Contents {} 0 R /Resources << >> >>",
+                config.page.size.width,
+                config.page.size.height,
+                4 + 2 * i
+            ));
+            let content = PdfWriter::content(charts, placements, config.stroke_width);
+            objects.push(format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                content.len(),
+                content
+            ));
+        }
+
+        let mut output: Vec<u8> = b"%PDF-1.4\n".to_vec();
+        let mut offsets: Vec<usize> = vec![];
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(output.len());
+            output.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).as_bytes());
+        }
+        let xref = output.len();
+        let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            table += &format!("{:010} 00000 n \n", offset);
+        }
+        table += &format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        );
+        output.extend_from_slice(table.as_bytes());
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::shape::{BaseChartConfig, ChartKind, Size},
+        repository::shapes::kinds::selector::ShapesGeneratorSelector,
+    };
+
+    fn gallery() -> Vec<Chart> {
+        [ChartKind::Star, ChartKind::Sunrise, ChartKind::Cardioid]
+            .iter()
+            .map(|kind| {
+                ShapesGeneratorSelector::select(kind).generate_chart(BaseChartConfig {
+                    kind: *kind,
+                    complexity: 12,
+                    mutation: None,
+                    randomizer: None,
+                    parameters: None,
+                })
+            })
+            .collect()
+    }
+
+    fn config(layout: GalleryLayout) -> VectorConfig {
+        VectorConfig {
+            page: Page {
+                size: Size {
+                    width: 595.0,
+                    height: 842.0,
+                },
+                margin: 36.0,
+            },
+            stroke_width: 0.5,
+            layout,
+        }
+    }
+
+    #[test]
+    fn test_page_per_chart() {
+        let actual = String::from_utf8(PdfWriter::write(
+            &gallery(),
+            &config(GalleryLayout::PagePerChart),
+        ))
+        .unwrap();
+        assert!(actual.starts_with("%PDF-1.4\n"));
+        assert!(actual.contains("/Kids [3 0 R 5 0 R 7 0 R] /Count 3"));
+        assert!(actual.ends_with("%%EOF\n"));
+
+        // every cross-reference entry points at the start of its object
+        let xref = actual.rfind("\nxref\n").unwrap() + 1;
+        let offsets: Vec<usize> = actual[xref..]
+            .lines()
+            .skip(3)
+            .take(8)
+            .map(|line| line[..10].parse().unwrap())
+            .collect();
+        for (i, offset) in offsets.iter().enumerate() {
+            assert!(actual[*offset..].starts_with(&format!("{} 0 obj", i + 1)));
+        }
+        let start: usize = actual.lines().rev().nth(1).unwrap().parse().unwrap();
+        assert_eq!(xref, start);
+    }
+
+    #[test]
+    fn test_grid_gallery() {
+        let actual = String::from_utf8(PdfWriter::write(
+            &gallery(),
+            &config(GalleryLayout::Grid { columns: 2 }),
+        ))
+        .unwrap();
+        assert!(actual.contains("/Count 1"));
+        // the star is one closed run of twelve orders
+        let star_runs = actual
+            .lines()
+            .filter(|line| line.ends_with(" S") && line.matches(" l").count() == 12)
+            .count();
+        assert_eq!(1, star_runs);
+    }
+}
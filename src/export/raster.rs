@@ -0,0 +1,113 @@
+use super::page::{Page, YAxis};
+use crate::{
+    analysis::{geometry::Bounds, spatial_index::SpatialIndex},
+    style::{palette::Color, styler::StyledSegment},
+};
+
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    // row-major, top row first
+    pub pixels: Vec<Color>,
+}
+
+pub struct RasterConfig {
+    // in pixels
+    pub page: Page,
+    pub background: Color,
+}
+
+pub struct Rasterizer;
+
+impl Rasterizer {
+    fn blend(under: &Color, over: &Color, coverage: f64) -> Color {
+        let alpha = coverage * over.a as f64 / 255.0;
+        let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * alpha).round() as u8;
+        Color {
+            r: channel(under.r, over.r),
+            g: channel(under.g, over.g),
+            b: channel(under.b, over.b),
+            a: channel(under.a, 255),
+        }
+    }
+
+    // anti-aliased strokes, `StyledSegment::width` in pixels; `bounds` is fitted into the page
+    pub fn render(segments: &[StyledSegment], bounds: &Bounds, config: &RasterConfig) -> Image {
+        let width = config.page.size.width.round().max(1.0) as u32;
+        let height = config.page.size.height.round().max(1.0) as u32;
+        let transform = config.page.fit(bounds, YAxis::Down);
+        let mut image = Image {
+            width,
+            height,
+            pixels: vec![config.background; (width * height) as usize],
+        };
+
+        for segment in segments {
+            let start = transform.apply(&segment.start);
+            let end = transform.apply(&segment.end);
+            let reach = segment.width / 2.0 + 0.5;
+            let min_x = (start.x.min(end.x) - reach).floor().max(0.0) as u32;
+            let min_y = (start.y.min(end.y) - reach).floor().max(0.0) as u32;
+            let max_x = ((start.x.max(end.x) + reach).ceil().max(0.0) as u32).min(width);
+            let max_y = ((start.y.max(end.y) + reach).ceil().max(0.0) as u32).min(height);
+            for y in min_y..max_y {
+                for x in min_x..max_x {
+                    let center = crate::model::shape::Point {
+                        x: x as f64 + 0.5,
+                        y: y as f64 + 0.5,
+                    };
+                    let distance = SpatialIndex::distance_to_segment(&center, &start, &end);
+                    let coverage = (reach - distance).clamp(0.0, 1.0);
+                    if coverage > 0.0 {
+                        let pixel = &mut image.pixels[(y * width + x) as usize];
+                        *pixel = Rasterizer::blend(pixel, &segment.color, coverage);
+                    }
+                }
+            }
+        }
+        image
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::shape::{Point, Size};
+
+    #[test]
+    fn test_line_rendering() {
+        let segments = vec![StyledSegment {
+            order: 0,
+            start: Point { x: 0.0, y: 0.0 },
+            end: Point { x: 1.0, y: 0.0 },
+            color: Color::rgb(255, 0, 0),
+            width: 1.0,
+        }];
+        let bounds = Bounds {
+            min: Point { x: 0.0, y: -1.0 },
+            max: Point { x: 1.0, y: 1.0 },
+        };
+        let image = Rasterizer::render(
+            &segments,
+            &bounds,
+            &RasterConfig {
+                page: Page {
+                    size: Size {
+                        width: 8.0,
+                        height: 8.0,
+                    },
+                    margin: 0.0,
+                },
+                background: Color::rgb(255, 255, 255),
+            },
+        );
+        let white = Color::rgb(255, 255, 255);
+        let red = Color::rgb(255, 0, 0);
+        // the line runs along y = 4, between pixel rows 3 and 4
+        assert_eq!(white, image.pixels[2 * 8 + 4]);
+        assert_eq!(Color::rgb(255, 128, 128), image.pixels[3 * 8 + 4]);
+        assert_eq!(Color::rgb(255, 128, 128), image.pixels[4 * 8 + 4]);
+        assert_ne!(red, image.pixels[4 * 8]);
+        assert_eq!(white, image.pixels[5 * 8 + 4]);
+    }
+}
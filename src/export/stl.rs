@@ -0,0 +1,87 @@
+use crate::model::mesh::Mesh;
+
+pub struct StlWriter;
+
+impl StlWriter {
+    pub fn ascii(mesh: &Mesh, name: &str) -> String {
+        let mut output = format!("solid {}\n", name);
+        for triangle in &mesh.triangles {
+            let n = mesh.normal(triangle);
+            output += &format!(
+                "  facet normal {:e} {:e} {:e}\n    outer loop\n",
+                n.x, n.y, n.z
+            );
+            for &i in triangle {
+                let v = mesh.vertices[i];
+                output += &format!("      vertex {:e} {:e} {:e}\n", v.x, v.y, v.z);
+            }
+            output += "    endloop\n  endfacet\n";
+        }
+        output += &format!("endsolid {}\n", name);
+        output
+    }
+
+    // 80 byte header, triangle count, then normal, vertices and attribute count per triangle, little endian
+    pub fn binary(mesh: &Mesh) -> Vec<u8> {
+        let mut output = vec![0_u8; 80];
+        output.extend_from_slice(&(mesh.triangles.len() as u32).to_le_bytes());
+        for triangle in &mesh.triangles {
+            let n = mesh.normal(triangle);
+            let vertices = triangle.map(|i| mesh.vertices[i]);
+            for p in std::iter::once(n).chain(vertices) {
+                for value in [p.x, p.y, p.z] {
+                    output.extend_from_slice(&(value as f32).to_le_bytes());
+                }
+            }
+            output.extend_from_slice(&0_u16.to_le_bytes());
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::shape::Point3;
+
+    fn triangle() -> Mesh {
+        Mesh {
+            vertices: vec![
+                Point3 {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+                Point3 {
+                    x: 1.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+                Point3 {
+                    x: 0.0,
+                    y: 1.0,
+                    z: 0.0,
+                },
+            ],
+            triangles: vec![[0, 1, 2]],
+        }
+    }
+
+    #[test]
+    fn test_stl_output() {
+        let ascii = StlWriter::ascii(&triangle(), "coaster");
+        assert!(ascii.starts_with("solid coaster\n  facet normal 0e0 0e0 1e0\n    outer loop\n"));
+        assert!(ascii.contains("      vertex 1e0 0e0 0e0\n"));
+        assert!(ascii.ends_with("    endloop\n  endfacet\nendsolid coaster\n"));
+
+        let binary = StlWriter::binary(&triangle());
+        assert_eq!(80 + 4 + 50, binary.len());
+        assert_eq!(1, u32::from_le_bytes(binary[80..84].try_into().unwrap()));
+        // normal z, then the second vertex x
+        assert_eq!(1.0, f32::from_le_bytes(binary[92..96].try_into().unwrap()));
+        assert_eq!(
+            1.0,
+            f32::from_le_bytes(binary[108..112].try_into().unwrap())
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+
+use super::page::Page;
+use crate::{
+    analysis::geometry::GeometryAnalyzer,
+    model::{
+        shape::{Chart, Point},
+        transform::Transform,
+    },
+};
+
+pub struct PlotterConfig {
+    // in millimetres
+    pub page: Page,
+    // points within this, in chart units, are joined into one stroke
+    pub merge_tolerance: f64,
+}
+
+// polylines drawn without lifting the pen
+pub struct StrokePlanner;
+
+impl StrokePlanner {
+    // walks unused orders from vertex to vertex, starting at odd-degree vertices so that each walk is as long as possible
+    pub fn chain(chart: &Chart, tolerance: f64) -> Vec<Vec<usize>> {
+        let vertex_of = GeometryAnalyzer::representatives(&chart.points, tolerance);
+        let edges: Vec<(usize, usize)> = chart
+            .orders
+            .iter()
+            .map(|order| {
+                (
+                    vertex_of[chart.point_index(order.link.0)],
+                    vertex_of[chart.point_index(order.link.1)],
+                )
+            })
+            .filter(|(start, end)| start != end)
+            .collect();
+        let mut adjacency: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (i, (start, end)) in edges.iter().enumerate() {
+            adjacency.entry(*start).or_default().push(i);
+            adjacency.entry(*end).or_default().push(i);
+        }
+        let mut used = vec![false; edges.len()];
+        let mut cursor: HashMap<usize, usize> = HashMap::new();
+        let mut next_edge = |vertex: usize, used: &mut Vec<bool>| -> Option<usize> {
+            let list = adjacency.get(&vertex)?;
+            let position = cursor.entry(vertex).or_insert(0);
+            while *position < list.len() {
+                let edge = list[*position];
+                *position += 1;
+                if !used[edge] {
+                    used[edge] = true;
+                    return Some(edge);
+                }
+            }
+            None
+        };
+
+        let mut degrees: HashMap<usize, usize> = HashMap::new();
+        for (start, end) in &edges {
+            *degrees.entry(*start).or_default() += 1;
+            *degrees.entry(*end).or_default() += 1;
+        }
+        let starts: Vec<usize> = edges
+            .iter()
+            .flat_map(|&(start, end)| [start, end])
+            .filter(|vertex| degrees[vertex] % 2 == 1)
+            .chain(edges.iter().map(|&(start, _)| start))
+            .collect();
+
+        let mut strokes: Vec<Vec<usize>> = vec![];
+        for start in starts {
+            let mut vertex = start;
+            let mut stroke = vec![vertex];
+            while let Some(edge) = next_edge(vertex, &mut used) {
+                let (a, b) = edges[edge];
+                vertex = if a == vertex { b } else { a };
+                stroke.push(vertex);
+            }
+            if stroke.len() > 1 {
+                strokes.push(stroke);
+            }
+        }
+        strokes
+    }
+
+    // consecutive orders continuing from the previous end, in drawing order; each run lists point indices
+    pub fn runs(chart: &Chart) -> Vec<Vec<usize>> {
+        let mut runs: Vec<Vec<usize>> = vec![];
+        for order in &chart.orders {
+            let start = chart.point_index(order.link.0);
+            let end = chart.point_index(order.link.1);
+            match runs.last_mut() {
+                Some(run) if run[run.len() - 1] == start => run.push(end),
+                _ => runs.push(vec![start, end]),
+            }
+        }
+        runs
+    }
+
+    fn distance(a: &Point, b: &Point) -> f64 {
+        (a.x - b.x).hypot(a.y - b.y)
+    }
+
+    // greedy nearest neighbour from `origin`, reversing strokes when their far end is closer
+    pub fn order(strokes: Vec<Vec<Point>>, origin: &Point) -> Vec<Vec<Point>> {
+        let mut remaining = strokes;
+        let mut ordered: Vec<Vec<Point>> = vec![];
+        let mut position = *origin;
+        while !remaining.is_empty() {
+            let (index, reverse, _) = remaining
+                .iter()
+                .enumerate()
+                .flat_map(|(i, stroke)| {
+                    [
+                        (i, false, StrokePlanner::distance(&position, &stroke[0])),
+                        (
+                            i,
+                            true,
+                            StrokePlanner::distance(&position, &stroke[stroke.len() - 1]),
+                        ),
+                    ]
+                })
+                .min_by(|a, b| a.2.total_cmp(&b.2))
+                .unwrap_or((0, false, 0.0));
+            let mut stroke = remaining.swap_remove(index);
+            if reverse {
+                stroke.reverse();
+            }
+            position = stroke[stroke.len() - 1];
+            ordered.push(stroke);
+        }
+        ordered
+    }
+
+    // chained, transformed and ordered strokes ready for a plotter
+    pub fn plan(chart: &Chart, transform: &Transform, tolerance: f64) -> Vec<Vec<Point>> {
+        let strokes = StrokePlanner::chain(chart, tolerance)
+            .iter()
+            .map(|stroke| {
+                stroke
+                    .iter()
+                    .map(|&i| transform.apply(&chart.points[i]))
+                    .collect()
+            })
+            .collect();
+        StrokePlanner::order(strokes, &Point { x: 0.0, y: 0.0 })
+    }
+
+    pub fn travel_distance(strokes: &[Vec<Point>], origin: &Point) -> f64 {
+        let mut position = *origin;
+        let mut total = 0.0;
+        for stroke in strokes {
+            total += StrokePlanner::distance(&position, &stroke[0]);
+            position = stroke[stroke.len() - 1];
+        }
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::shape::{BaseChartConfig, ChartKind, Order},
+        repository::shapes::kinds::selector::ShapesGeneratorSelector,
+    };
+
+    #[test]
+    fn test_linear_chaining() {
+        let chart = Chart {
+            points: vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 1.0, y: 0.0 },
+                Point { x: 2.0, y: 0.0 },
+                Point { x: 2.0, y: 0.0 },
+                Point { x: 3.0, y: 0.0 },
+            ],
+            orders: (0..4).map(|i| Order { link: (i, i + 1) }).collect(),
+        };
+        assert_eq!(vec![vec![0, 1, 2, 4]], StrokePlanner::chain(&chart, 1e-9));
+        // a tolerance far below the coordinates still joins the coincident points
+        assert_eq!(vec![vec![0, 1, 2, 4]], StrokePlanner::chain(&chart, 1e-300));
+    }
+
+    #[test]
+    fn test_runs() {
+        let chart = Chart {
+            points: vec![Point { x: 0.0, y: 0.0 }; 4],
+            orders: vec![
+                Order { link: (0, 1) },
+                Order { link: (1, 2) },
+                Order { link: (3, 0) },
+                Order { link: (0, 4) },
+            ],
+        };
+        assert_eq!(
+            vec![vec![0, 1, 2], vec![3, 0, 0]],
+            StrokePlanner::runs(&chart)
+        );
+    }
+
+    #[test]
+    fn test_star_chaining() {
+        // every vertex of a star has an even degree, so it is drawn in one stroke
+        let chart =
+            ShapesGeneratorSelector::select(&ChartKind::Star).generate_chart(BaseChartConfig {
+                kind: ChartKind::Star,
+                complexity: 7,
+                mutation: None,
+                randomizer: None,
+                parameters: None,
+            });
+        let strokes = StrokePlanner::chain(&chart, 1e-9);
+        assert_eq!(1, strokes.len());
+        assert_eq!(8, strokes[0].len());
+    }
+
+    #[test]
+    fn test_stroke_ordering() {
+        let stroke = |x: f64| vec![Point { x, y: 0.0 }, Point { x: x + 1.0, y: 0.0 }];
+        let strokes = vec![stroke(10.0), stroke(2.0), stroke(6.0)];
+        let origin = Point { x: 0.0, y: 0.0 };
+        let before = StrokePlanner::travel_distance(&strokes, &origin);
+        let ordered = StrokePlanner::order(strokes, &origin);
+        assert_eq!(
+            vec![2.0, 6.0, 10.0],
+            ordered.iter().map(|s| s[0].x).collect::<Vec<f64>>()
+        );
+        assert!(StrokePlanner::travel_distance(&ordered, &origin) < before);
+    }
+}
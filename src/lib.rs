@@ -1,2 +1,7 @@
+pub mod analysis;
+pub mod animation;
+pub mod export;
 pub mod model;
 pub mod repository;
+pub mod solid;
+pub mod style;
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use super::shape::Point3;
+
+// triangles wind counter-clockwise seen from outside
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mesh {
+    pub vertices: Vec<Point3>,
+    pub triangles: Vec<[usize; 3]>,
+}
+
+impl Mesh {
+    pub fn new() -> Mesh {
+        Mesh {
+            vertices: vec![],
+            triangles: vec![],
+        }
+    }
+
+    pub fn append(&mut self, other: &Mesh) {
+        let offset = self.vertices.len();
+        self.vertices.extend_from_slice(&other.vertices);
+        self.triangles.extend(
+            other
+                .triangles
+                .iter()
+                .map(|t| [t[0] + offset, t[1] + offset, t[2] + offset]),
+        );
+    }
+
+    // unit normal by the right-hand rule; zero for degenerate triangles
+    pub fn normal(&self, triangle: &[usize; 3]) -> Point3 {
+        let [a, b, c] = triangle.map(|i| self.vertices[i]);
+        let (u, v) = (
+            (b.x - a.x, b.y - a.y, b.z - a.z),
+            (c.x - a.x, c.y - a.y, c.z - a.z),
+        );
+        let n = (
+            u.1 * v.2 - u.2 * v.1,
+            u.2 * v.0 - u.0 * v.2,
+            u.0 * v.1 - u.1 * v.0,
+        );
+        let length = (n.0 * n.0 + n.1 * n.1 + n.2 * n.2).sqrt();
+        if length == 0.0 {
+            return Point3 {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            };
+        }
+        Point3 {
+            x: n.0 / length,
+            y: n.1 / length,
+            z: n.2 / length,
+        }
+    }
+
+    // every edge is shared by exactly two triangles running it in opposite directions
+    pub fn is_watertight(&self) -> bool {
+        let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+        for t in &self.triangles {
+            for i in 0..3 {
+                *edges.entry((t[i], t[(i + 1) % 3])).or_default() += 1;
+            }
+        }
+        !edges.is_empty()
+            && edges
+                .iter()
+                .all(|(&(a, b), &count)| count == 1 && edges.get(&(b, a)) == Some(&1))
+    }
+
+    // signed volume, positive for closed meshes wound outwards
+    pub fn volume(&self) -> f64 {
+        self.triangles
+            .iter()
+            .map(|t| {
+                let [a, b, c] = t.map(|i| self.vertices[i]);
+                (a.x * (b.y * c.z - b.z * c.y) - a.y * (b.x * c.z - b.z * c.x)
+                    + a.z * (b.x * c.y - b.y * c.x))
+                    / 6.0
+            })
+            .sum()
+    }
+}
+
+impl Default for Mesh {
+    fn default() -> Self {
+        Mesh::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tetrahedron() {
+        let p = |x: f64, y: f64, z: f64| Point3 { x, y, z };
+        let mut mesh = Mesh {
+            vertices: vec![
+                p(0.0, 0.0, 0.0),
+                p(1.0, 0.0, 0.0),
+                p(0.0, 1.0, 0.0),
+                p(0.0, 0.0, 1.0),
+            ],
+            triangles: vec![[0, 2, 1], [0, 1, 3], [1, 2, 3], [0, 3, 2]],
+        };
+        assert!(mesh.is_watertight());
+        assert!((mesh.volume() - 1.0 / 6.0).abs() < 1e-12);
+        assert_eq!(p(0.0, 0.0, -1.0), mesh.normal(&[0, 2, 1]));
+
+        mesh.triangles.pop();
+        assert!(!mesh.is_watertight());
+    }
+}
@@ -1 +1,4 @@
+pub mod mesh;
+pub mod projection;
 pub mod shape;
+pub mod transform;
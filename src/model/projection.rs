@@ -0,0 +1,93 @@
+use super::shape::{Chart, Chart3, Point, Point3};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Projection {
+    Orthographic,
+    // eye on the +z axis at `distance` from the origin, looking towards it; points must stay in front of the eye
+    Perspective { distance: f64 },
+}
+
+// turns the scene by `yaw` about the y axis, then by `pitch` about the x axis, before projecting onto the xy plane
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    pub yaw: f64,
+    pub pitch: f64,
+    pub projection: Projection,
+}
+
+impl Camera {
+    pub fn view(&self, point: &Point3) -> Point3 {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        let x = point.x * cos_yaw + point.z * sin_yaw;
+        let z = -point.x * sin_yaw + point.z * cos_yaw;
+        Point3 {
+            x,
+            y: point.y * cos_pitch - z * sin_pitch,
+            z: point.y * sin_pitch + z * cos_pitch,
+        }
+    }
+
+    pub fn project(&self, point: &Point3) -> Point {
+        let view = self.view(point);
+        let scale = match self.projection {
+            Projection::Orthographic => 1.0,
+            Projection::Perspective { distance } => distance / (distance - view.z),
+        };
+        Point {
+            x: view.x * scale,
+            y: view.y * scale,
+        }
+    }
+
+    pub fn project_chart(&self, chart: &Chart3) -> Chart {
+        Chart {
+            points: chart.points.iter().map(|p| self.project(p)).collect(),
+            orders: chart.orders.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::shape::Order;
+
+    #[test]
+    fn test_projection() {
+        let p = |x: f64, y: f64, z: f64| Point3 { x, y, z };
+        let front = Camera {
+            yaw: 0.0,
+            pitch: 0.0,
+            projection: Projection::Orthographic,
+        };
+        assert_eq!(Point { x: 1.0, y: 2.0 }, front.project(&p(1.0, 2.0, 3.0)));
+
+        // a quarter turn shows the z axis to the right
+        let side = Camera {
+            yaw: 90.0_f64.to_radians(),
+            ..front
+        };
+        assert!((side.project(&p(0.0, 0.0, 1.0)).x - 1.0).abs() < 1e-12);
+
+        // nearer points spread further from the centre
+        let perspective = Camera {
+            projection: Projection::Perspective { distance: 4.0 },
+            ..front
+        };
+        assert_eq!(
+            Point { x: 2.0, y: 2.0 },
+            perspective.project(&p(1.0, 1.0, 2.0))
+        );
+        assert_eq!(
+            Point { x: 0.5, y: 0.5 },
+            perspective.project(&p(1.0, 1.0, -4.0))
+        );
+
+        let chart = perspective.project_chart(&Chart3 {
+            points: vec![p(0.0, 0.0, 0.0), p(1.0, 1.0, 2.0)],
+            orders: vec![Order { link: (0, 1) }],
+        });
+        assert_eq!(vec![Order { link: (0, 1) }], chart.orders);
+    }
+}
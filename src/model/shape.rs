@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct Point {
     pub x: f64,
     pub y: f64,
@@ -15,16 +15,60 @@ impl Point {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Size {
     pub width: f64,
     pub height: f64,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Order {
     pub link: (u64, u64),
 }
 
+#[derive(Debug, PartialEq)]
+pub struct Chart {
+    pub points: Vec<Point>,
+    pub orders: Vec<Order>,
+}
+
+// `depth` is the lowest complexity at which the point appears, `parent` the division it was inserted into
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointAttributes {
+    pub depth: u64,
+    pub parent: Option<u64>,
+}
+
+// `division` numbers the segment within its generation, `parent` is the division it was split from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrderAttributes {
+    pub depth: u64,
+    pub division: u64,
+    pub parent: Option<u64>,
+    pub rule: Option<u64>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct AttributedChart {
+    pub chart: Chart,
+    pub point_attributes: Vec<PointAttributes>,
+    pub order_attributes: Vec<OrderAttributes>,
+}
+
+impl Chart {
+    // Loop orders close onto `points.len()`, which wraps back to the first point
+    pub fn point_index(&self, index: u64) -> usize {
+        (index % self.points.len() as u64) as usize
+    }
+}
+
 impl PartialEq for Point {
     fn eq(&self, other: &Self) -> bool {
         (self.x - other.x).abs() < f64::EPSILON * 1e1
@@ -32,11 +76,13 @@ impl PartialEq for Point {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Mutation {
     pub size: f64,
     pub angle: f64,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Randomizer {
     pub size_amplitude: f64,
     pub size_seed: u64,
@@ -44,27 +90,302 @@ pub struct Randomizer {
     pub angle_seed: u64,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ChartKind {
     BinaryTree,
+    Cardioid,
     Clover,
+    Deltoid,
     FoldCCurve,
     FoldDragon,
     KochCurve,
     KochTriangleInner,
     KochTriangleOuter,
+    Nephroid,
     Star,
     Starmine,
     Sunrise,
     Sunset,
     TriCis,
     TriTrans,
+    Rose,
+    Hypotrochoid,
+    Epitrochoid,
+    Superformula,
+}
+
+impl ChartKind {
+    // stored by position in binary charts, so new kinds may only be appended
+    pub const ALL: [ChartKind; 20] = [
+        ChartKind::BinaryTree,
+        ChartKind::Cardioid,
+        ChartKind::Clover,
+        ChartKind::Deltoid,
+        ChartKind::FoldCCurve,
+        ChartKind::FoldDragon,
+        ChartKind::KochCurve,
+        ChartKind::KochTriangleInner,
+        ChartKind::KochTriangleOuter,
+        ChartKind::Nephroid,
+        ChartKind::Star,
+        ChartKind::Starmine,
+        ChartKind::Sunrise,
+        ChartKind::Sunset,
+        ChartKind::TriCis,
+        ChartKind::TriTrans,
+        ChartKind::Rose,
+        ChartKind::Hypotrochoid,
+        ChartKind::Epitrochoid,
+        ChartKind::Superformula,
+    ];
+}
+
+// radius of the circle that Star, Sunrise and the times tables place their points on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CircleParameters {
+    pub radius: f64,
+}
+
+impl Default for CircleParameters {
+    fn default() -> Self {
+        CircleParameters { radius: 1.0 }
+    }
+}
+
+// `density` points are sampled per unit of complexity, unless a `tolerance` is set:
+// then the curve is subdivided until it strays at most that far from its chords; 0 counts as none
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CloverParameters {
+    pub density: u64,
+    pub tolerance: Option<f64>,
+}
+
+impl Default for CloverParameters {
+    fn default() -> Self {
+        CloverParameters {
+            density: 40,
+            tolerance: None,
+        }
+    }
+}
+
+// distance from the centre to each side of the initial triangle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KochTriangleParameters {
+    pub offset: f64,
+}
+
+impl Default for KochTriangleParameters {
+    fn default() -> Self {
+        KochTriangleParameters {
+            offset: 1.0 / 3.0_f64.sqrt(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StarmineParameters {
+    pub outer_radius: f64,
+    pub inner_radius: f64,
+}
+
+impl Default for StarmineParameters {
+    fn default() -> Self {
+        StarmineParameters {
+            outer_radius: 1.0,
+            inner_radius: 0.25,
+        }
+    }
+}
+
+// r = cos(numerator / denominator * θ); a `tolerance` samples by chord deviation instead of by turning
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoseParameters {
+    pub numerator: u64,
+    pub denominator: u64,
+    pub tolerance: Option<f64>,
+}
+
+impl Default for RoseParameters {
+    fn default() -> Self {
+        RoseParameters {
+            numerator: 5,
+            denominator: 4,
+            tolerance: None,
+        }
+    }
+}
+
+// a circle of `rolling_radius` rolls inside (hypotrochoid) or outside (epitrochoid) one of `fixed_radius`,
+// drawing with a pen `pen_distance` from its centre
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrochoidParameters {
+    pub fixed_radius: u64,
+    pub rolling_radius: u64,
+    pub pen_distance: f64,
+    pub tolerance: Option<f64>,
 }
 
+impl Default for TrochoidParameters {
+    fn default() -> Self {
+        TrochoidParameters {
+            fixed_radius: 5,
+            rolling_radius: 3,
+            pen_distance: 5.0,
+            tolerance: None,
+        }
+    }
+}
+
+// Gielis' superformula with m = m_numerator / m_denominator
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SuperformulaParameters {
+    pub m_numerator: u64,
+    pub m_denominator: u64,
+    pub n1: f64,
+    pub n2: f64,
+    pub n3: f64,
+    pub a: f64,
+    pub b: f64,
+    pub tolerance: Option<f64>,
+}
+
+impl Default for SuperformulaParameters {
+    fn default() -> Self {
+        SuperformulaParameters {
+            m_numerator: 6,
+            m_denominator: 1,
+            n1: 1.0,
+            n2: 7.0,
+            n3: 8.0,
+            a: 1.0,
+            b: 1.0,
+            tolerance: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KindParameters {
+    Circle(CircleParameters),
+    Clover(CloverParameters),
+    KochTriangle(KochTriangleParameters),
+    Starmine(StarmineParameters),
+    Rose(RoseParameters),
+    Trochoid(TrochoidParameters),
+    Superformula(SuperformulaParameters),
+}
+
+// `parameters` left as `None`, or set for another kind, keeps the kind's defaults
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct BaseChartConfig {
     pub kind: ChartKind,
     pub complexity: u64,
     pub mutation: Option<Mutation>,
     pub randomizer: Option<Randomizer>,
+    pub parameters: Option<KindParameters>,
+}
+
+impl BaseChartConfig {
+    pub fn circle_parameters(&self) -> CircleParameters {
+        match self.parameters {
+            Some(KindParameters::Circle(parameters)) => parameters,
+            _ => CircleParameters::default(),
+        }
+    }
+
+    pub fn clover_parameters(&self) -> CloverParameters {
+        match self.parameters {
+            Some(KindParameters::Clover(parameters)) => parameters,
+            _ => CloverParameters::default(),
+        }
+    }
+
+    pub fn koch_triangle_parameters(&self) -> KochTriangleParameters {
+        match self.parameters {
+            Some(KindParameters::KochTriangle(parameters)) => parameters,
+            _ => KochTriangleParameters::default(),
+        }
+    }
+
+    pub fn starmine_parameters(&self) -> StarmineParameters {
+        match self.parameters {
+            Some(KindParameters::Starmine(parameters)) => parameters,
+            _ => StarmineParameters::default(),
+        }
+    }
+
+    pub fn rose_parameters(&self) -> RoseParameters {
+        match self.parameters {
+            Some(KindParameters::Rose(parameters)) => parameters,
+            _ => RoseParameters::default(),
+        }
+    }
+
+    pub fn trochoid_parameters(&self) -> TrochoidParameters {
+        match self.parameters {
+            Some(KindParameters::Trochoid(parameters)) => parameters,
+            _ => TrochoidParameters::default(),
+        }
+    }
+
+    pub fn superformula_parameters(&self) -> SuperformulaParameters {
+        match self.parameters {
+            Some(KindParameters::Superformula(parameters)) => parameters,
+            _ => SuperformulaParameters::default(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Chart3 {
+    pub points: Vec<Point3>,
+    pub orders: Vec<Order>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpatialChartKind {
+    BinaryTree,
+    HilbertCurve,
+    MengerSponge,
+}
+
+// radians the branching plane of the spatial tree turns by at every level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpatialTreeParameters {
+    pub yaw: f64,
+}
+
+impl Default for SpatialTreeParameters {
+    fn default() -> Self {
+        SpatialTreeParameters {
+            yaw: 90.0_f64.to_radians(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpatialKindParameters {
+    BinaryTree(SpatialTreeParameters),
+}
+
+// `parameters` left as `None` keeps the kind's defaults
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpatialChartConfig {
+    pub kind: SpatialChartKind,
+    pub complexity: u64,
+    pub mutation: Option<Mutation>,
+    pub randomizer: Option<Randomizer>,
+    pub parameters: Option<SpatialKindParameters>,
+}
+
+impl SpatialChartConfig {
+    pub fn tree_parameters(&self) -> SpatialTreeParameters {
+        match self.parameters {
+            Some(SpatialKindParameters::BinaryTree(parameters)) => parameters,
+            _ => SpatialTreeParameters::default(),
+        }
+    }
 }
 
 #[cfg(test)]
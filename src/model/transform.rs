@@ -0,0 +1,204 @@
+use super::shape::{Chart, Order, Point};
+
+// affine map: x' = a * x + c * y + e, y' = b * x + d * y + f
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+impl Transform {
+    pub fn identity() -> Self {
+        Transform {
+            a: 1.0,
+            b: 0.0,
+            c: 0.0,
+            d: 1.0,
+            e: 0.0,
+            f: 0.0,
+        }
+    }
+
+    pub fn translate(x: f64, y: f64) -> Self {
+        Transform {
+            e: x,
+            f: y,
+            ..Transform::identity()
+        }
+    }
+
+    pub fn rotate(radian: f64) -> Self {
+        let sin = radian.sin();
+        let cos = radian.cos();
+        Transform {
+            a: cos,
+            b: sin,
+            c: -sin,
+            d: cos,
+            e: 0.0,
+            f: 0.0,
+        }
+    }
+
+    pub fn rotate_around(center: &Point, radian: f64) -> Self {
+        Transform::translate(-center.x, -center.y)
+            .then(&Transform::rotate(radian))
+            .then(&Transform::translate(center.x, center.y))
+    }
+
+    pub fn scale(x: f64, y: f64) -> Self {
+        Transform {
+            a: x,
+            d: y,
+            ..Transform::identity()
+        }
+    }
+
+    pub fn shear(x: f64, y: f64) -> Self {
+        Transform {
+            b: y,
+            c: x,
+            ..Transform::identity()
+        }
+    }
+
+    // mirror across the line through the origin at `radian` from the x axis
+    pub fn reflect(radian: f64) -> Self {
+        let sin = (2.0 * radian).sin();
+        let cos = (2.0 * radian).cos();
+        Transform {
+            a: cos,
+            b: sin,
+            c: sin,
+            d: -cos,
+            e: 0.0,
+            f: 0.0,
+        }
+    }
+
+    // `self` first, then `next`
+    pub fn then(&self, next: &Transform) -> Self {
+        Transform {
+            a: next.a * self.a + next.c * self.b,
+            b: next.b * self.a + next.d * self.b,
+            c: next.a * self.c + next.c * self.d,
+            d: next.b * self.c + next.d * self.d,
+            e: next.a * self.e + next.c * self.f + next.e,
+            f: next.b * self.e + next.d * self.f + next.f,
+        }
+    }
+
+    pub fn invert(&self) -> Option<Self> {
+        let determinant = self.a * self.d - self.b * self.c;
+        if determinant.abs() < f64::EPSILON {
+            return None;
+        }
+        let a = self.d / determinant;
+        let b = -self.b / determinant;
+        let c = -self.c / determinant;
+        let d = self.a / determinant;
+        Some(Transform {
+            a,
+            b,
+            c,
+            d,
+            e: -(a * self.e + c * self.f),
+            f: -(b * self.e + d * self.f),
+        })
+    }
+
+    // `count` rotations evenly spread around the origin, starting with the identity
+    pub fn rotations(count: u64) -> Vec<Self> {
+        (0..count)
+            .map(|i| Transform::rotate(2.0 * std::f64::consts::PI * i as f64 / count as f64))
+            .collect()
+    }
+
+    pub fn apply(&self, point: &Point) -> Point {
+        Point {
+            x: self.a * point.x + self.c * point.y + self.e,
+            y: self.b * point.x + self.d * point.y + self.f,
+        }
+    }
+
+    pub fn apply_points(&self, points: &[Point]) -> Vec<Point> {
+        points.iter().map(|p| self.apply(p)).collect()
+    }
+
+    pub fn apply_chart(&self, chart: &Chart) -> Chart {
+        Chart {
+            points: self.apply_points(&chart.points),
+            orders: chart.orders.clone(),
+        }
+    }
+}
+
+impl Chart {
+    // one copy per transform, with each copy's orders shifted onto its own points
+    pub fn replicate(&self, transforms: &[Transform]) -> Chart {
+        let point_count = self.points.len() as u64;
+        let points = transforms
+            .iter()
+            .flat_map(|transform| transform.apply_points(&self.points))
+            .collect();
+        let orders = (0..transforms.len() as u64)
+            .flat_map(|copy| {
+                self.orders.iter().map(move |order| Order {
+                    link: (
+                        self.point_index(order.link.0) as u64 + copy * point_count,
+                        self.point_index(order.link.1) as u64 + copy * point_count,
+                    ),
+                })
+            })
+            .collect();
+        Chart { points, orders }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transform_composition() {
+        let transform = Transform::scale(2.0, 3.0)
+            .then(&Transform::rotate(90.0_f64.to_radians()))
+            .then(&Transform::translate(1.0, -1.0));
+        let actual = transform.apply(&Point { x: 1.0, y: 1.0 });
+        assert_eq!(Point { x: -2.0, y: 1.0 }, actual);
+    }
+
+    #[test]
+    fn test_transform_inversion() {
+        let transform = Transform::shear(0.5, 0.0)
+            .then(&Transform::rotate_around(&Point { x: 1.0, y: 2.0 }, 1.0))
+            .then(&Transform::reflect(0.3));
+        let point = Point { x: 0.25, y: -0.75 };
+        let actual = transform.invert().unwrap().apply(&transform.apply(&point));
+        assert_eq!(point, actual);
+        assert_eq!(None, Transform::scale(0.0, 1.0).invert());
+    }
+
+    #[test]
+    fn test_chart_replication() {
+        let chart = Chart {
+            points: vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 }],
+            orders: vec![Order { link: (0, 1) }],
+        };
+        let actual = chart.replicate(&Transform::rotations(2));
+        let expect = Chart {
+            points: vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 1.0, y: 0.0 },
+                Point { x: 0.0, y: 0.0 },
+                Point { x: -1.0, y: 0.0 },
+            ],
+            orders: vec![Order { link: (0, 1) }, Order { link: (2, 3) }],
+        };
+        assert_eq!(expect, actual);
+    }
+}
@@ -0,0 +1,633 @@
+use crate::model::shape::{
+    BaseChartConfig, Chart, ChartKind, CircleParameters, CloverParameters, KindParameters,
+    KochTriangleParameters, Mutation, Order, Point, Randomizer, RoseParameters, StarmineParameters,
+    SuperformulaParameters, TrochoidParameters,
+};
+
+const MAGIC: &[u8; 4] = b"FVCH";
+// readers accept any minor version of their major version, skipping header fields and trailing data they do not know
+const MAJOR_VERSION: u8 = 1;
+// 1.1 appends the config's kind parameters to the header, 1.2 their sampling tolerance
+const MINOR_VERSION: u8 = 2;
+
+const FLAG_DOUBLE: u8 = 1;
+const FLAG_CONFIG: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Precision {
+    Single,
+    Double,
+}
+
+// `config` is absent for charts that were not generated from one, such as composed scenes
+#[derive(Debug, PartialEq)]
+pub struct ChartRecord {
+    pub config: Option<BaseChartConfig>,
+    pub chart: Chart,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DecodeError {
+    InvalidMagic,
+    UnsupportedVersion(u8),
+    UnknownKind(u8),
+    Truncated,
+    Malformed,
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, count: usize) -> Result<&'a [u8], DecodeError> {
+        let end = self
+            .position
+            .checked_add(count)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or(DecodeError::Truncated)?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn byte(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u64, DecodeError> {
+        let mut value = 0_u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(DecodeError::Malformed)
+    }
+
+    fn signed(&mut self) -> Result<i64, DecodeError> {
+        let value = self.varint()?;
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    fn f64(&mut self) -> Result<f64, DecodeError> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn f32(&mut self) -> Result<f32, DecodeError> {
+        Ok(f32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+}
+
+pub struct ChartWriter;
+
+impl ChartWriter {
+    fn varint(output: &mut Vec<u8>, mut value: u64) {
+        while value >= 0x80 {
+            output.push((value as u8 & 0x7f) | 0x80);
+            value >>= 7;
+        }
+        output.push(value as u8);
+    }
+
+    fn signed(output: &mut Vec<u8>, value: i64) {
+        ChartWriter::varint(output, ((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    fn header(record: &ChartRecord, precision: Precision) -> Vec<u8> {
+        let mut header: Vec<u8> = vec![];
+        let mut flags = 0;
+        if precision == Precision::Double {
+            flags |= FLAG_DOUBLE;
+        }
+        if record.config.is_some() {
+            flags |= FLAG_CONFIG;
+        }
+        header.push(flags);
+        if let Some(config) = &record.config {
+            let kind = ChartKind::ALL
+                .iter()
+                .position(|kind| *kind == config.kind)
+                .unwrap();
+            header.push(kind as u8);
+            ChartWriter::varint(&mut header, config.complexity);
+            match &config.mutation {
+                Some(mutation) => {
+                    header.push(1);
+                    header.extend_from_slice(&mutation.size.to_le_bytes());
+                    header.extend_from_slice(&mutation.angle.to_le_bytes());
+                }
+                None => header.push(0),
+            }
+            match &config.randomizer {
+                Some(randomizer) => {
+                    header.push(1);
+                    header.extend_from_slice(&randomizer.size_amplitude.to_le_bytes());
+                    ChartWriter::varint(&mut header, randomizer.size_seed);
+                    header.extend_from_slice(&randomizer.angle_amplitude.to_le_bytes());
+                    ChartWriter::varint(&mut header, randomizer.angle_seed);
+                }
+                None => header.push(0),
+            }
+        }
+        ChartWriter::varint(&mut header, record.chart.points.len() as u64);
+        ChartWriter::varint(&mut header, record.chart.orders.len() as u64);
+        if let Some(config) = &record.config {
+            ChartWriter::parameters(&mut header, &config.parameters);
+        }
+        header
+    }
+
+    fn tolerance(output: &mut Vec<u8>, tolerance: Option<f64>) {
+        match tolerance {
+            Some(tolerance) => {
+                output.push(1);
+                output.extend_from_slice(&tolerance.to_le_bytes());
+            }
+            None => output.push(0),
+        }
+    }
+
+    fn parameters(output: &mut Vec<u8>, parameters: &Option<KindParameters>) {
+        match parameters {
+            None => output.push(0),
+            Some(KindParameters::Circle(circle)) => {
+                output.push(1);
+                output.extend_from_slice(&circle.radius.to_le_bytes());
+            }
+            Some(KindParameters::Clover(clover)) => {
+                output.push(2);
+                ChartWriter::varint(output, clover.density);
+                ChartWriter::tolerance(output, clover.tolerance);
+            }
+            Some(KindParameters::KochTriangle(triangle)) => {
+                output.push(3);
+                output.extend_from_slice(&triangle.offset.to_le_bytes());
+            }
+            Some(KindParameters::Starmine(starmine)) => {
+                output.push(4);
+                output.extend_from_slice(&starmine.outer_radius.to_le_bytes());
+                output.extend_from_slice(&starmine.inner_radius.to_le_bytes());
+            }
+            Some(KindParameters::Rose(rose)) => {
+                output.push(5);
+                ChartWriter::varint(output, rose.numerator);
+                ChartWriter::varint(output, rose.denominator);
+                ChartWriter::tolerance(output, rose.tolerance);
+            }
+            Some(KindParameters::Trochoid(trochoid)) => {
+                output.push(6);
+                ChartWriter::varint(output, trochoid.fixed_radius);
+                ChartWriter::varint(output, trochoid.rolling_radius);
+                output.extend_from_slice(&trochoid.pen_distance.to_le_bytes());
+                ChartWriter::tolerance(output, trochoid.tolerance);
+            }
+            Some(KindParameters::Superformula(superformula)) => {
+                output.push(7);
+                ChartWriter::varint(output, superformula.m_numerator);
+                ChartWriter::varint(output, superformula.m_denominator);
+                for value in [
+                    superformula.n1,
+                    superformula.n2,
+                    superformula.n3,
+                    superformula.a,
+                    superformula.b,
+                ] {
+                    output.extend_from_slice(&value.to_le_bytes());
+                }
+                ChartWriter::tolerance(output, superformula.tolerance);
+            }
+        }
+    }
+
+    // orders are grouped into runs where both ends step by one, each stored as
+    // the start relative to the previous group's, the span and the number of extra orders
+    fn orders(output: &mut Vec<u8>, orders: &[Order]) {
+        let mut previous_start = 0_i64;
+        let mut i = 0;
+        while i < orders.len() {
+            let (start, end) = orders[i].link;
+            let mut run = 0;
+            while orders.get(i + run + 1).map(|order| order.link)
+                == Some((start + run as u64 + 1, end + run as u64 + 1))
+            {
+                run += 1;
+            }
+            ChartWriter::signed(output, start as i64 - previous_start);
+            ChartWriter::signed(output, end as i64 - start as i64);
+            ChartWriter::varint(output, run as u64);
+            previous_start = (start + run as u64) as i64;
+            i += run + 1;
+        }
+    }
+
+    pub fn write(record: &ChartRecord, precision: Precision) -> Vec<u8> {
+        let mut output: Vec<u8> = MAGIC.to_vec();
+        output.push(MAJOR_VERSION);
+        output.push(MINOR_VERSION);
+        let header = ChartWriter::header(record, precision);
+        ChartWriter::varint(&mut output, header.len() as u64);
+        output.extend_from_slice(&header);
+        for p in &record.chart.points {
+            match precision {
+                Precision::Single => {
+                    output.extend_from_slice(&(p.x as f32).to_le_bytes());
+                    output.extend_from_slice(&(p.y as f32).to_le_bytes());
+                }
+                Precision::Double => {
+                    output.extend_from_slice(&p.x.to_le_bytes());
+                    output.extend_from_slice(&p.y.to_le_bytes());
+                }
+            }
+        }
+        ChartWriter::orders(&mut output, &record.chart.orders);
+        output
+    }
+}
+
+pub struct ChartReader;
+
+impl ChartReader {
+    fn config(reader: &mut ByteReader) -> Result<BaseChartConfig, DecodeError> {
+        let kind = reader.byte()?;
+        let kind = *ChartKind::ALL
+            .get(kind as usize)
+            .ok_or(DecodeError::UnknownKind(kind))?;
+        let complexity = reader.varint()?;
+        let mutation = match reader.byte()? {
+            0 => None,
+            1 => Some(Mutation {
+                size: reader.f64()?,
+                angle: reader.f64()?,
+            }),
+            _ => return Err(DecodeError::Malformed),
+        };
+        let randomizer = match reader.byte()? {
+            0 => None,
+            1 => Some(Randomizer {
+                size_amplitude: reader.f64()?,
+                size_seed: reader.varint()?,
+                angle_amplitude: reader.f64()?,
+                angle_seed: reader.varint()?,
+            }),
+            _ => return Err(DecodeError::Malformed),
+        };
+        Ok(BaseChartConfig {
+            kind,
+            complexity,
+            mutation,
+            randomizer,
+            parameters: None,
+        })
+    }
+
+    // written from 1.2 on
+    fn tolerance(reader: &mut ByteReader, minor: u8) -> Result<Option<f64>, DecodeError> {
+        if minor < 2 {
+            return Ok(None);
+        }
+        match reader.byte()? {
+            0 => Ok(None),
+            1 => Ok(Some(reader.f64()?)),
+            _ => Err(DecodeError::Malformed),
+        }
+    }
+
+    fn parameters(
+        reader: &mut ByteReader,
+        minor: u8,
+    ) -> Result<Option<KindParameters>, DecodeError> {
+        Ok(match reader.byte()? {
+            0 => None,
+            1 => Some(KindParameters::Circle(CircleParameters {
+                radius: reader.f64()?,
+            })),
+            2 => Some(KindParameters::Clover(CloverParameters {
+                density: reader.varint()?,
+                tolerance: ChartReader::tolerance(reader, minor)?,
+            })),
+            3 => Some(KindParameters::KochTriangle(KochTriangleParameters {
+                offset: reader.f64()?,
+            })),
+            4 => Some(KindParameters::Starmine(StarmineParameters {
+                outer_radius: reader.f64()?,
+                inner_radius: reader.f64()?,
+            })),
+            5 => Some(KindParameters::Rose(RoseParameters {
+                numerator: reader.varint()?,
+                denominator: reader.varint()?,
+                tolerance: ChartReader::tolerance(reader, minor)?,
+            })),
+            6 => Some(KindParameters::Trochoid(TrochoidParameters {
+                fixed_radius: reader.varint()?,
+                rolling_radius: reader.varint()?,
+                pen_distance: reader.f64()?,
+                tolerance: ChartReader::tolerance(reader, minor)?,
+            })),
+            7 => Some(KindParameters::Superformula(SuperformulaParameters {
+                m_numerator: reader.varint()?,
+                m_denominator: reader.varint()?,
+                n1: reader.f64()?,
+                n2: reader.f64()?,
+                n3: reader.f64()?,
+                a: reader.f64()?,
+                b: reader.f64()?,
+                tolerance: ChartReader::tolerance(reader, minor)?,
+            })),
+            // parameters of a kind added after this version; the rest of the header is skipped with them
+            _ if minor > MINOR_VERSION => None,
+            _ => return Err(DecodeError::Malformed),
+        })
+    }
+
+    pub fn read(bytes: &[u8]) -> Result<ChartRecord, DecodeError> {
+        let mut reader = ByteReader { bytes, position: 0 };
+        if reader.take(4).map_err(|_| DecodeError::InvalidMagic)? != MAGIC {
+            return Err(DecodeError::InvalidMagic);
+        }
+        let major = reader.byte()?;
+        if major != MAJOR_VERSION {
+            return Err(DecodeError::UnsupportedVersion(major));
+        }
+        let minor = reader.byte()?;
+        let header_length = reader.varint()? as usize;
+        let mut header = ByteReader {
+            bytes: reader.take(header_length)?,
+            position: 0,
+        };
+        let flags = header.byte()?;
+        let mut config = if flags & FLAG_CONFIG != 0 {
+            Some(ChartReader::config(&mut header)?)
+        } else {
+            None
+        };
+        let point_count = header.varint()? as usize;
+        let order_count = header.varint()? as usize;
+        if let Some(config) = config.as_mut().filter(|_| minor >= 1) {
+            config.parameters = ChartReader::parameters(&mut header, minor)?;
+        }
+
+        let point_size = if flags & FLAG_DOUBLE != 0 { 16 } else { 8 };
+        if point_count
+            .checked_mul(point_size)
+            .ok_or(DecodeError::Malformed)?
+            > bytes.len() - reader.position
+        {
+            return Err(DecodeError::Truncated);
+        }
+        let mut points: Vec<Point> = Vec::with_capacity(point_count);
+        for _ in 0..point_count {
+            points.push(if flags & FLAG_DOUBLE != 0 {
+                Point {
+                    x: reader.f64()?,
+                    y: reader.f64()?,
+                }
+            } else {
+                Point {
+                    x: reader.f32()? as f64,
+                    y: reader.f32()? as f64,
+                }
+            });
+        }
+
+        // a Loop order closes on `point_count` itself, which `Chart::point_index` wraps to the first point
+        let in_range = |index: i64| index >= 0 && index as u64 <= points.len() as u64;
+        let mut orders: Vec<Order> = vec![];
+        let mut previous_start = 0_i64;
+        while orders.len() < order_count {
+            let start = previous_start
+                .checked_add(reader.signed()?)
+                .ok_or(DecodeError::Malformed)?;
+            let end = start
+                .checked_add(reader.signed()?)
+                .ok_or(DecodeError::Malformed)?;
+            let run = reader.varint()?;
+            if points.is_empty() || run as usize >= order_count - orders.len() {
+                return Err(DecodeError::Malformed);
+            }
+            // where the run ends, if it stays within the points from where it starts
+            let last = |index: i64| {
+                index
+                    .checked_add(run as i64)
+                    .filter(|last| in_range(index) && in_range(*last))
+            };
+            let (last_start, _) = last(start).zip(last(end)).ok_or(DecodeError::Malformed)?;
+            for i in 0..=run {
+                orders.push(Order {
+                    link: (start as u64 + i, end as u64 + i),
+                });
+            }
+            previous_start = last_start;
+        }
+        Ok(ChartRecord {
+            config,
+            chart: Chart { points, orders },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::shapes::kinds::selector::ShapesGeneratorSelector;
+
+    fn record(kind: ChartKind, complexity: u64) -> ChartRecord {
+        let config = BaseChartConfig {
+            kind,
+            complexity,
+            mutation: Some(Mutation {
+                size: 1.0,
+                angle: 1.0,
+            }),
+            randomizer: Some(Randomizer {
+                size_amplitude: 0.25,
+                size_seed: 300,
+                angle_amplitude: 0.5,
+                angle_seed: 7,
+            }),
+            parameters: None,
+        };
+        ChartRecord {
+            config: Some(config),
+            chart: ShapesGeneratorSelector::select(&kind).generate_chart(config),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for kind in [
+            ChartKind::FoldDragon,
+            ChartKind::BinaryTree,
+            ChartKind::Star,
+        ] {
+            let expect = record(kind, 6);
+            let bytes = ChartWriter::write(&expect, Precision::Double);
+            assert_eq!(Ok(&expect), ChartReader::read(&bytes).as_ref());
+
+            // single precision rounds once and then stays stable
+            let single =
+                ChartReader::read(&ChartWriter::write(&expect, Precision::Single)).unwrap();
+            assert_eq!(expect.chart.orders, single.chart.orders);
+            assert_eq!(
+                ChartWriter::write(&single, Precision::Single),
+                ChartWriter::write(&expect, Precision::Single)
+            );
+        }
+        let scene = ChartRecord {
+            config: None,
+            chart: Chart {
+                points: vec![Point { x: 1.5, y: -2.0 }],
+                orders: vec![Order { link: (0, 1) }, Order { link: (0, 0) }],
+            },
+        };
+        let bytes = ChartWriter::write(&scene, Precision::Double);
+        assert_eq!(Ok(scene), ChartReader::read(&bytes));
+    }
+
+    #[test]
+    fn test_malformed_orders() {
+        let scene = |orders| ChartRecord {
+            config: None,
+            chart: Chart {
+                points: vec![Point { x: 1.5, y: -2.0 }, Point { x: 0.0, y: 1.0 }],
+                orders,
+            },
+        };
+        // only the closing link of a loop may reach the point count
+        let bytes = ChartWriter::write(&scene(vec![Order { link: (1, 2) }]), Precision::Double);
+        assert!(ChartReader::read(&bytes).is_ok());
+        let bytes = ChartWriter::write(&scene(vec![Order { link: (1, 3) }]), Precision::Double);
+        assert_eq!(Err(DecodeError::Malformed), ChartReader::read(&bytes));
+        let bytes = ChartWriter::write(
+            &scene(vec![
+                Order { link: (0, 1) },
+                Order { link: (1, 2) },
+                Order { link: (2, 3) },
+            ]),
+            Precision::Double,
+        );
+        assert_eq!(Err(DecodeError::Malformed), ChartReader::read(&bytes));
+
+        // a span that overflows is rejected rather than wrapped
+        let mut bytes = ChartWriter::write(&scene(vec![Order { link: (0, 0) }]), Precision::Double);
+        bytes.truncate(bytes.len() - 3);
+        ChartWriter::signed(&mut bytes, i64::MAX);
+        ChartWriter::signed(&mut bytes, i64::MAX);
+        ChartWriter::varint(&mut bytes, 0);
+        assert_eq!(Err(DecodeError::Malformed), ChartReader::read(&bytes));
+    }
+
+    #[test]
+    fn test_linear_orders_collapse() {
+        let expect = record(ChartKind::FoldDragon, 12);
+        let bytes = ChartWriter::write(&expect, Precision::Single);
+        let points = expect.chart.points.len() * 8;
+        // one group of three bytes covers every order
+        assert!(bytes.len() - points < 80);
+        assert_eq!(
+            expect.chart.orders,
+            ChartReader::read(&bytes).unwrap().chart.orders
+        );
+    }
+
+    #[test]
+    fn test_versioning() {
+        let expect = record(ChartKind::KochCurve, 3);
+        let bytes = ChartWriter::write(&expect, Precision::Double);
+        assert_eq!(
+            Err(DecodeError::InvalidMagic),
+            ChartReader::read(b"PNG\0rest")
+        );
+        assert_eq!(
+            Err(DecodeError::Truncated),
+            ChartReader::read(&bytes[..bytes.len() - 1])
+        );
+        let mut newer_major = bytes.clone();
+        newer_major[4] = MAJOR_VERSION + 1;
+        assert_eq!(
+            Err(DecodeError::UnsupportedVersion(MAJOR_VERSION + 1)),
+            ChartReader::read(&newer_major)
+        );
+
+        // a newer minor version with an extra header field and a trailing section still reads
+        let header_length = bytes[6] as usize;
+        let mut newer_minor = bytes[..6].to_vec();
+        newer_minor[5] = MINOR_VERSION + 1;
+        newer_minor.push(header_length as u8 + 2);
+        newer_minor.extend_from_slice(&bytes[7..7 + header_length]);
+        newer_minor.extend_from_slice(&[0xab, 0xcd]);
+        newer_minor.extend_from_slice(&bytes[7 + header_length..]);
+        newer_minor.extend_from_slice(b"future section");
+        assert_eq!(Ok(&expect), ChartReader::read(&newer_minor).as_ref());
+
+        // parameters of a kind this version does not know yet are skipped, not rejected
+        let mut unknown_parameters = newer_minor.clone();
+        unknown_parameters[6 + header_length] = 0xfe;
+        assert_eq!(Ok(expect), ChartReader::read(&unknown_parameters));
+        let mut current_minor = bytes.clone();
+        current_minor[6 + header_length] = 0xfe;
+        assert_eq!(
+            Err(DecodeError::Malformed),
+            ChartReader::read(&current_minor)
+        );
+
+        // 1.0 headers end at the counts and keep the default parameters
+        let mut older_minor = bytes[..6].to_vec();
+        older_minor[5] = 0;
+        older_minor.push(header_length as u8 - 1);
+        older_minor.extend_from_slice(&bytes[7..6 + header_length]);
+        older_minor.extend_from_slice(&bytes[7 + header_length..]);
+        assert_eq!(
+            Ok(record(ChartKind::KochCurve, 3)),
+            ChartReader::read(&older_minor)
+        );
+    }
+
+    #[test]
+    fn test_parameters_round_trip() {
+        let mut expect = record(ChartKind::Starmine, 5);
+        let parameters = Some(KindParameters::Starmine(StarmineParameters {
+            outer_radius: 2.0,
+            inner_radius: 0.5,
+        }));
+        expect.config.as_mut().unwrap().parameters = parameters;
+        let bytes = ChartWriter::write(&expect, Precision::Double);
+        assert_eq!(Ok(expect), ChartReader::read(&bytes));
+
+        let mut expect = record(ChartKind::Superformula, 32);
+        expect.config.as_mut().unwrap().parameters =
+            Some(KindParameters::Superformula(SuperformulaParameters {
+                m_numerator: 7,
+                m_denominator: 2,
+                n3: 0.5,
+                ..SuperformulaParameters::default()
+            }));
+        let bytes = ChartWriter::write(&expect, Precision::Single);
+        assert_eq!(expect.config, ChartReader::read(&bytes).unwrap().config);
+
+        let mut expect = record(ChartKind::Clover, 3);
+        let clover = |tolerance| {
+            Some(KindParameters::Clover(CloverParameters {
+                density: 40,
+                tolerance,
+            }))
+        };
+        expect.config.as_mut().unwrap().parameters = clover(Some(1e-3));
+        let bytes = ChartWriter::write(&expect, Precision::Double);
+        assert_eq!(Ok(&expect), ChartReader::read(&bytes).as_ref());
+
+        // 1.1 parameters carry no tolerance
+        expect.config.as_mut().unwrap().parameters = clover(None);
+        let bytes = ChartWriter::write(&expect, Precision::Double);
+        let header_length = bytes[6] as usize;
+        let mut older_minor = bytes[..6].to_vec();
+        older_minor[5] = 1;
+        older_minor.push(header_length as u8 - 1);
+        older_minor.extend_from_slice(&bytes[7..6 + header_length]);
+        older_minor.extend_from_slice(&bytes[7 + header_length..]);
+        assert_eq!(Ok(expect), ChartReader::read(&older_minor));
+    }
+}
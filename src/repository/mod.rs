@@ -1 +1,6 @@
+pub mod binary;
+pub mod presets;
+pub mod scene;
+pub mod schema;
 pub mod shapes;
+pub mod validation;
@@ -0,0 +1,504 @@
+use serde::Deserialize;
+
+use super::validation::ConfigValidator;
+use crate::model::shape::{
+    BaseChartConfig, ChartKind, CircleParameters, CloverParameters, KindParameters,
+    KochTriangleParameters, Mutation, Randomizer, RoseParameters, StarmineParameters,
+    SuperformulaParameters, TrochoidParameters,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preset {
+    pub name: String,
+    pub config: BaseChartConfig,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PresetError {
+    Parse(String),
+    UnknownKind { name: String, kind: String },
+    DuplicateName(String),
+    Invalid { name: String, reason: String },
+}
+
+// file layout shared by TOML and JSON: a `presets` array of tables
+#[derive(Deserialize)]
+struct PresetFile {
+    presets: Vec<PresetEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PresetEntry {
+    name: String,
+    kind: String,
+    complexity: u64,
+    mutation: Option<MutationEntry>,
+    randomizer: Option<RandomizerEntry>,
+    parameters: Option<ParametersEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MutationEntry {
+    size: f64,
+    angle: f64,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RandomizerEntry {
+    size_amplitude: f64,
+    size_seed: u64,
+    angle_amplitude: f64,
+    angle_seed: u64,
+}
+
+// fields left out keep the kind's defaults
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ParametersEntry {
+    radius: Option<f64>,
+    density: Option<u64>,
+    offset: Option<f64>,
+    outer_radius: Option<f64>,
+    inner_radius: Option<f64>,
+    numerator: Option<u64>,
+    denominator: Option<u64>,
+    fixed_radius: Option<u64>,
+    rolling_radius: Option<u64>,
+    pen_distance: Option<f64>,
+    m_numerator: Option<u64>,
+    m_denominator: Option<u64>,
+    n1: Option<f64>,
+    n2: Option<f64>,
+    n3: Option<f64>,
+    a: Option<f64>,
+    b: Option<f64>,
+    tolerance: Option<f64>,
+}
+
+impl ParametersEntry {
+    // `None` when a field is set that the kind does not use
+    fn into_parameters(self, kind: &ChartKind) -> Option<KindParameters> {
+        let set = [
+            ("radius", self.radius.is_some()),
+            ("density", self.density.is_some()),
+            ("offset", self.offset.is_some()),
+            ("outer_radius", self.outer_radius.is_some()),
+            ("inner_radius", self.inner_radius.is_some()),
+            ("numerator", self.numerator.is_some()),
+            ("denominator", self.denominator.is_some()),
+            ("fixed_radius", self.fixed_radius.is_some()),
+            ("rolling_radius", self.rolling_radius.is_some()),
+            ("pen_distance", self.pen_distance.is_some()),
+            ("m_numerator", self.m_numerator.is_some()),
+            ("m_denominator", self.m_denominator.is_some()),
+            ("n1", self.n1.is_some()),
+            ("n2", self.n2.is_some()),
+            ("n3", self.n3.is_some()),
+            ("a", self.a.is_some()),
+            ("b", self.b.is_some()),
+            ("tolerance", self.tolerance.is_some()),
+        ];
+        let only = |fields: &[&str]| {
+            set.iter()
+                .all(|(field, is_set)| !is_set || fields.contains(field))
+        };
+        match kind {
+            ChartKind::Cardioid
+            | ChartKind::Deltoid
+            | ChartKind::Nephroid
+            | ChartKind::Star
+            | ChartKind::Sunrise
+                if only(&["radius"]) =>
+            {
+                Some(KindParameters::Circle(CircleParameters {
+                    radius: self.radius.unwrap_or(CircleParameters::default().radius),
+                }))
+            }
+            ChartKind::Clover if only(&["density", "tolerance"]) => {
+                Some(KindParameters::Clover(CloverParameters {
+                    density: self.density.unwrap_or(CloverParameters::default().density),
+                    tolerance: self.tolerance,
+                }))
+            }
+            ChartKind::KochTriangleInner | ChartKind::KochTriangleOuter if only(&["offset"]) => {
+                Some(KindParameters::KochTriangle(KochTriangleParameters {
+                    offset: self
+                        .offset
+                        .unwrap_or(KochTriangleParameters::default().offset),
+                }))
+            }
+            ChartKind::Starmine if only(&["outer_radius", "inner_radius"]) => {
+                let defaults = StarmineParameters::default();
+                Some(KindParameters::Starmine(StarmineParameters {
+                    outer_radius: self.outer_radius.unwrap_or(defaults.outer_radius),
+                    inner_radius: self.inner_radius.unwrap_or(defaults.inner_radius),
+                }))
+            }
+            ChartKind::Rose if only(&["numerator", "denominator", "tolerance"]) => {
+                let defaults = RoseParameters::default();
+                Some(KindParameters::Rose(RoseParameters {
+                    numerator: self.numerator.unwrap_or(defaults.numerator),
+                    denominator: self.denominator.unwrap_or(defaults.denominator),
+                    tolerance: self.tolerance,
+                }))
+            }
+            ChartKind::Hypotrochoid | ChartKind::Epitrochoid
+                if only(&[
+                    "fixed_radius",
+                    "rolling_radius",
+                    "pen_distance",
+                    "tolerance",
+                ]) =>
+            {
+                let defaults = TrochoidParameters::default();
+                Some(KindParameters::Trochoid(TrochoidParameters {
+                    fixed_radius: self.fixed_radius.unwrap_or(defaults.fixed_radius),
+                    rolling_radius: self.rolling_radius.unwrap_or(defaults.rolling_radius),
+                    pen_distance: self.pen_distance.unwrap_or(defaults.pen_distance),
+                    tolerance: self.tolerance,
+                }))
+            }
+            ChartKind::Superformula
+                if only(&[
+                    "m_numerator",
+                    "m_denominator",
+                    "n1",
+                    "n2",
+                    "n3",
+                    "a",
+                    "b",
+                    "tolerance",
+                ]) =>
+            {
+                let defaults = SuperformulaParameters::default();
+                Some(KindParameters::Superformula(SuperformulaParameters {
+                    m_numerator: self.m_numerator.unwrap_or(defaults.m_numerator),
+                    m_denominator: self.m_denominator.unwrap_or(defaults.m_denominator),
+                    n1: self.n1.unwrap_or(defaults.n1),
+                    n2: self.n2.unwrap_or(defaults.n2),
+                    n3: self.n3.unwrap_or(defaults.n3),
+                    a: self.a.unwrap_or(defaults.a),
+                    b: self.b.unwrap_or(defaults.b),
+                    tolerance: self.tolerance,
+                }))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl PresetEntry {
+    // kinds are written as their variant names, e.g. "FoldDragon"
+    fn into_preset(self) -> Result<Preset, PresetError> {
+        let kind = ChartKind::ALL
+            .iter()
+            .find(|kind| format!("{:?}", kind) == self.kind)
+            .ok_or(PresetError::UnknownKind {
+                name: self.name.clone(),
+                kind: self.kind.clone(),
+            })?;
+        let parameters = match self.parameters {
+            Some(parameters) => Some(parameters.into_parameters(kind).ok_or(
+                PresetError::Invalid {
+                    name: self.name.clone(),
+                    reason: "parameters do not belong to this kind".to_string(),
+                },
+            )?),
+            None => None,
+        };
+        Ok(Preset {
+            name: self.name,
+            config: BaseChartConfig {
+                kind: *kind,
+                complexity: self.complexity,
+                mutation: self.mutation.map(|m| Mutation {
+                    size: m.size,
+                    angle: m.angle,
+                }),
+                randomizer: self.randomizer.map(|r| Randomizer {
+                    size_amplitude: r.size_amplitude,
+                    size_seed: r.size_seed,
+                    angle_amplitude: r.angle_amplitude,
+                    angle_seed: r.angle_seed,
+                }),
+                parameters,
+            },
+        })
+    }
+}
+
+pub struct PresetLibrary {
+    presets: Vec<Preset>,
+}
+
+impl PresetLibrary {
+    fn preset(
+        name: &str,
+        kind: ChartKind,
+        complexity: u64,
+        randomizer: Option<(f64, u64, f64, u64)>,
+    ) -> Preset {
+        Preset {
+            name: name.to_string(),
+            config: BaseChartConfig {
+                kind,
+                complexity,
+                mutation: randomizer.map(|_| Mutation {
+                    size: 1.0,
+                    angle: 1.0,
+                }),
+                randomizer: randomizer.map(
+                    |(size_amplitude, size_seed, angle_amplitude, angle_seed)| Randomizer {
+                        size_amplitude,
+                        size_seed,
+                        angle_amplitude,
+                        angle_seed,
+                    },
+                ),
+                parameters: None,
+            },
+        }
+    }
+
+    // one preset per kind with a generator; `Sunset` has none yet
+    pub fn builtin() -> PresetLibrary {
+        let still = Some((0.0, 0, 0.0, 0));
+        PresetLibrary {
+            presets: vec![
+                PresetLibrary::preset(
+                    "binary-tree",
+                    ChartKind::BinaryTree,
+                    8,
+                    Some((0.1, 1, 0.1, 2)),
+                ),
+                PresetLibrary::preset("cardioid", ChartKind::Cardioid, 200, None),
+                PresetLibrary::preset("clover", ChartKind::Clover, 5, None),
+                PresetLibrary::preset("deltoid", ChartKind::Deltoid, 200, None),
+                PresetLibrary::preset("c-curve", ChartKind::FoldCCurve, 12, still),
+                PresetLibrary::preset(
+                    "dragon",
+                    ChartKind::FoldDragon,
+                    12,
+                    Some((0.05, 7, 0.05, 11)),
+                ),
+                PresetLibrary::preset("koch-curve", ChartKind::KochCurve, 5, still),
+                PresetLibrary::preset(
+                    "koch-anti-snowflake",
+                    ChartKind::KochTriangleInner,
+                    5,
+                    still,
+                ),
+                PresetLibrary::preset("koch-snowflake", ChartKind::KochTriangleOuter, 5, still),
+                PresetLibrary::preset("nephroid", ChartKind::Nephroid, 200, None),
+                PresetLibrary::preset("star", ChartKind::Star, 7, None),
+                PresetLibrary::preset("starmine", ChartKind::Starmine, 30, None),
+                PresetLibrary::preset("sunrise", ChartKind::Sunrise, 60, None),
+                PresetLibrary::preset("tri-cis", ChartKind::TriCis, 7, still),
+                PresetLibrary::preset("tri-trans", ChartKind::TriTrans, 7, Some((0.1, 5, 0.0, 0))),
+                PresetLibrary::preset("rose", ChartKind::Rose, 72, None),
+                PresetLibrary::preset("spirograph", ChartKind::Hypotrochoid, 72, None),
+                PresetLibrary::preset("epitrochoid", ChartKind::Epitrochoid, 72, None),
+                PresetLibrary::preset("superformula", ChartKind::Superformula, 72, None),
+            ],
+        }
+    }
+
+    fn from_file(file: PresetFile) -> Result<PresetLibrary, PresetError> {
+        let mut library = PresetLibrary { presets: vec![] };
+        for entry in file.presets {
+            let preset = entry.into_preset()?;
+            if library.get(&preset.name).is_some() {
+                return Err(PresetError::DuplicateName(preset.name));
+            }
+            library.insert(preset)?;
+        }
+        Ok(library)
+    }
+
+    pub fn from_toml(text: &str) -> Result<PresetLibrary, PresetError> {
+        let file: PresetFile =
+            toml::from_str(text).map_err(|error| PresetError::Parse(error.to_string()))?;
+        PresetLibrary::from_file(file)
+    }
+
+    pub fn from_json(text: &str) -> Result<PresetLibrary, PresetError> {
+        let file: PresetFile =
+            serde_json::from_str(text).map_err(|error| PresetError::Parse(error.to_string()))?;
+        PresetLibrary::from_file(file)
+    }
+
+    pub fn list(&self) -> Vec<&str> {
+        self.presets
+            .iter()
+            .map(|preset| preset.name.as_str())
+            .collect()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Preset> {
+        self.presets.iter().find(|preset| preset.name == name)
+    }
+
+    // replaces a preset of the same name, so loaded files can override the built-in set
+    pub fn insert(&mut self, preset: Preset) -> Result<(), PresetError> {
+        PresetLibrary::validate(&preset)?;
+        match self.presets.iter_mut().find(|p| p.name == preset.name) {
+            Some(existing) => *existing = preset,
+            None => self.presets.push(preset),
+        }
+        Ok(())
+    }
+
+    pub fn extend(&mut self, other: PresetLibrary) -> Result<(), PresetError> {
+        for preset in other.presets {
+            self.insert(preset)?;
+        }
+        Ok(())
+    }
+
+    // checks that the preset can be generated without panicking
+    pub fn validate(preset: &Preset) -> Result<(), PresetError> {
+        let invalid = |reason: String| PresetError::Invalid {
+            name: preset.name.clone(),
+            reason,
+        };
+        if preset.name.trim().is_empty() {
+            return Err(invalid("name is empty".to_string()));
+        }
+        ConfigValidator::estimate(&preset.config).map_err(|error| invalid(error.to_string()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::shapes::kinds::selector::ShapesGeneratorSelector;
+
+    #[test]
+    fn test_builtin_presets() {
+        let library = PresetLibrary::builtin();
+        for kind in ChartKind::ALL {
+            let covered = library
+                .list()
+                .iter()
+                .any(|name| library.get(name).unwrap().config.kind == kind);
+            assert_eq!(kind != ChartKind::Sunset, covered);
+        }
+        for name in library.list() {
+            let preset = library.get(name).unwrap();
+            assert_eq!(Ok(()), PresetLibrary::validate(preset));
+            let chart =
+                ShapesGeneratorSelector::select(&preset.config.kind).generate_chart(preset.config);
+            assert!(!chart.points.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_loading() {
+        let toml = r#"
+[[presets]]
+name = "swirl"
+kind = "FoldDragon"
+complexity = 10
+mutation = { size = 1.0, angle = 0.9 }
+randomizer = { size_amplitude = 0.2, size_seed = 42, angle_amplitude = 0.1, angle_seed = 7 }
+
+[[presets]]
+name = "dragon"
+kind = "FoldDragon"
+complexity = 6
+mutation = { size = 1.0, angle = 1.0 }
+randomizer = { size_amplitude = 0.0, size_seed = 0, angle_amplitude = 0.0, angle_seed = 0 }
+"#;
+        let json = r#"{"presets": [
+            {"name": "swirl", "kind": "FoldDragon", "complexity": 10,
+             "mutation": {"size": 1.0, "angle": 0.9},
+             "randomizer": {"size_amplitude": 0.2, "size_seed": 42, "angle_amplitude": 0.1, "angle_seed": 7}},
+            {"name": "dragon", "kind": "FoldDragon", "complexity": 6,
+             "mutation": {"size": 1.0, "angle": 1.0},
+             "randomizer": {"size_amplitude": 0.0, "size_seed": 0, "angle_amplitude": 0.0, "angle_seed": 0}}
+        ]}"#;
+        let from_toml = PresetLibrary::from_toml(toml).unwrap();
+        let from_json = PresetLibrary::from_json(json).unwrap();
+        assert_eq!(vec!["swirl", "dragon"], from_toml.list());
+        assert_eq!(from_toml.get("swirl"), from_json.get("swirl"));
+        assert_eq!(
+            Some(Randomizer {
+                size_amplitude: 0.2,
+                size_seed: 42,
+                angle_amplitude: 0.1,
+                angle_seed: 7,
+            }),
+            from_toml.get("swirl").unwrap().config.randomizer
+        );
+
+        // loaded presets override built-ins of the same name
+        let mut library = PresetLibrary::builtin();
+        let count = library.list().len();
+        library.extend(from_toml).unwrap();
+        assert_eq!(count + 1, library.list().len());
+        assert_eq!(6, library.get("dragon").unwrap().config.complexity);
+
+        let starmine = PresetLibrary::from_toml(
+            "[[presets]]\nname = \"wide\"\nkind = \"Starmine\"\ncomplexity = 12\nparameters = { inner_radius = 0.5 }\n",
+        )
+        .unwrap();
+        assert_eq!(
+            Some(KindParameters::Starmine(StarmineParameters {
+                outer_radius: 1.0,
+                inner_radius: 0.5,
+            })),
+            starmine.get("wide").unwrap().config.parameters
+        );
+        let rose = PresetLibrary::from_json(
+            r#"{"presets": [{"name": "rose", "kind": "Rose", "complexity": 72, "parameters": {"denominator": 7, "tolerance": 0.001}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            Some(KindParameters::Rose(RoseParameters {
+                numerator: 5,
+                denominator: 7,
+                tolerance: Some(0.001),
+            })),
+            rose.get("rose").unwrap().config.parameters
+        );
+    }
+
+    #[test]
+    fn test_invalid_presets() {
+        let entry = |kind: &str, complexity: u64| {
+            format!(
+                "[[presets]]\nname = \"p\"\nkind = \"{}\"\ncomplexity = {}\n",
+                kind, complexity
+            )
+        };
+        assert_eq!(
+            Err(PresetError::UnknownKind {
+                name: "p".to_string(),
+                kind: "Spiral".to_string()
+            }),
+            PresetLibrary::from_toml(&entry("Spiral", 3)).map(|_| ())
+        );
+        assert_eq!(
+            Err(PresetError::DuplicateName("p".to_string())),
+            PresetLibrary::from_toml(&(entry("Star", 3) + &entry("Star", 4))).map(|_| ())
+        );
+        for (kind, complexity) in [("Star", 0), ("Sunset", 3), ("FoldDragon", 3)] {
+            assert!(matches!(
+                PresetLibrary::from_toml(&entry(kind, complexity)),
+                Err(PresetError::Invalid { .. })
+            ));
+        }
+        assert!(matches!(
+            PresetLibrary::from_toml(&(entry("Star", 5) + "parameters = { density = 10 }\n")),
+            Err(PresetError::Invalid { .. })
+        ));
+        assert!(matches!(
+            PresetLibrary::from_json("{\"presets\": [{\"name\": \"p\"}]}"),
+            Err(PresetError::Parse(_))
+        ));
+    }
+}
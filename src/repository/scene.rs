@@ -0,0 +1,207 @@
+use std::collections::HashSet;
+
+use crate::{
+    analysis::geometry::GeometryAnalyzer,
+    model::{
+        shape::{BaseChartConfig, Chart, Order, Point},
+        transform::Transform,
+    },
+};
+
+use super::shapes::kinds::selector::ShapesGeneratorSelector;
+
+pub struct Layer {
+    pub chart: Chart,
+    pub transform: Transform,
+}
+
+impl Layer {
+    pub fn generate(config: BaseChartConfig, transform: Transform) -> Self {
+        let generator = ShapesGeneratorSelector::select(&config.kind);
+        Layer {
+            chart: generator.generate_chart(config),
+            transform,
+        }
+    }
+}
+
+pub struct Scene {
+    pub layers: Vec<Layer>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SceneError {
+    // merging needs a finite tolerance above zero
+    InvalidTolerance,
+}
+
+impl Scene {
+    // points within `merge_tolerance` of an earlier one are merged into it; orders collapsed by the merge are dropped,
+    // and orders joining the same two points are kept once
+    pub fn compose(&self, merge_tolerance: Option<f64>) -> Result<Chart, SceneError> {
+        if merge_tolerance.is_some_and(|tolerance| !tolerance.is_finite() || tolerance <= 0.0) {
+            return Err(SceneError::InvalidTolerance);
+        }
+        let placed: Vec<Point> = self
+            .layers
+            .iter()
+            .flat_map(|layer| layer.chart.points.iter().map(|p| layer.transform.apply(p)))
+            .collect();
+        let representatives = match merge_tolerance {
+            Some(tolerance) => GeometryAnalyzer::representatives(&placed, tolerance),
+            None => (0..placed.len()).collect(),
+        };
+        let mut points: Vec<Point> = vec![];
+        let mut index_of: Vec<u64> = Vec::with_capacity(placed.len());
+        for (i, point) in placed.into_iter().enumerate() {
+            if representatives[i] == i {
+                points.push(point);
+                index_of.push(points.len() as u64 - 1);
+            } else {
+                index_of.push(index_of[representatives[i]]);
+            }
+        }
+
+        let mut orders: Vec<Order> = vec![];
+        let mut links: HashSet<(u64, u64)> = HashSet::new();
+        let mut offset = 0;
+        for layer in &self.layers {
+            let chart = &layer.chart;
+            for order in &chart.orders {
+                let start = index_of[offset + chart.point_index(order.link.0)];
+                let end = index_of[offset + chart.point_index(order.link.1)];
+                if start == end && order.link.0 != order.link.1 {
+                    continue;
+                }
+                if merge_tolerance.is_some() && !links.insert((start.min(end), start.max(end))) {
+                    continue;
+                }
+                orders.push(Order { link: (start, end) });
+            }
+            offset += chart.points.len();
+        }
+        Ok(Chart { points, orders })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    use crate::model::shape::{ChartKind, Mutation, Randomizer};
+
+    fn dragon_config() -> BaseChartConfig {
+        BaseChartConfig {
+            kind: ChartKind::FoldDragon,
+            complexity: 5,
+            mutation: Some(Mutation {
+                size: 1.0,
+                angle: 1.0,
+            }),
+            randomizer: Some(Randomizer {
+                size_amplitude: 0.0,
+                size_seed: 0,
+                angle_amplitude: 0.0,
+                angle_seed: 0,
+            }),
+            parameters: None,
+        }
+    }
+
+    #[test]
+    fn test_quad_dragon_composition() {
+        let start = Point { x: -1.0, y: 0.0 };
+        let scene = Scene {
+            layers: (0..4)
+                .map(|i| {
+                    Layer::generate(
+                        dragon_config(),
+                        Transform::rotate_around(&start, i as f64 * 90.0_f64.to_radians()),
+                    )
+                })
+                .collect(),
+        };
+        let separate = scene.compose(None).unwrap();
+        let merged = scene.compose(Some(1e-9)).unwrap();
+
+        assert_eq!(4 * 17, separate.points.len());
+        assert_eq!(4 * 16, separate.orders.len());
+        assert_eq!(Order { link: (17, 18) }, separate.orders[16]);
+        // the rotated copies share their start point and meet on lattice points
+        assert_eq!(49, merged.points.len());
+        assert_eq!(4 * 16, merged.orders.len());
+        assert_eq!(0, merged.orders[16].link.0);
+    }
+
+    #[test]
+    fn test_loop_order_composition() {
+        let square = Chart {
+            points: vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 1.0, y: 0.0 },
+                Point { x: 1.0, y: 1.0 },
+            ],
+            orders: vec![
+                Order { link: (0, 1) },
+                Order { link: (1, 2) },
+                Order { link: (2, 3) },
+            ],
+        };
+        let scene = Scene {
+            layers: vec![
+                Layer {
+                    chart: Chart {
+                        points: square.points.clone(),
+                        orders: square.orders.clone(),
+                    },
+                    transform: Transform::identity(),
+                },
+                Layer {
+                    chart: square,
+                    transform: Transform::translate(2.0, 0.0),
+                },
+            ],
+        };
+        let actual = scene.compose(None).unwrap();
+        assert_eq!(Order { link: (2, 0) }, actual.orders[2]);
+        assert_eq!(Order { link: (5, 3) }, actual.orders[5]);
+    }
+
+    #[test]
+    fn test_overlapping_composition() {
+        let layer = |transform| Layer {
+            chart: Chart {
+                points: vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 }],
+                orders: vec![Order { link: (0, 1) }],
+            },
+            transform,
+        };
+        // the mirrored copy draws the same edge the other way round
+        let scene = Scene {
+            layers: vec![
+                layer(Transform::identity()),
+                layer(Transform::reflect(PI / 2.0).then(&Transform::translate(1.0, 0.0))),
+            ],
+        };
+        let merged = scene.compose(Some(1e-9)).unwrap();
+        assert_eq!(2, merged.points.len());
+        assert_eq!(vec![Order { link: (0, 1) }], merged.orders);
+        assert_eq!(2, scene.compose(None).unwrap().orders.len());
+
+        // a tolerance far below the coordinates only merges coincident points
+        let stacked = Scene {
+            layers: vec![layer(Transform::identity()), layer(Transform::identity())],
+        };
+        assert_eq!(merged, stacked.compose(Some(1e-300)).unwrap());
+        // the mirrored copy's far end is rounded off zero, so it stays apart
+        assert_eq!(3, scene.compose(Some(1e-300)).unwrap().points.len());
+
+        for tolerance in [0.0, -1.0, f64::NAN] {
+            assert_eq!(
+                Err(SceneError::InvalidTolerance),
+                scene.compose(Some(tolerance))
+            );
+        }
+    }
+}
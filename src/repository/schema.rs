@@ -0,0 +1,565 @@
+use serde::Serialize;
+
+use crate::{
+    model::shape::{
+        BaseChartConfig, ChartKind, CircleParameters, CloverParameters, KindParameters,
+        KochTriangleParameters, Mutation, Randomizer, RoseParameters, StarmineParameters,
+        SuperformulaParameters, TrochoidParameters,
+    },
+    repository::shapes::kinds::binarytree::BinaryTree,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ParameterRange {
+    Integer { min: u64, max: u64, default: u64 },
+    Float { min: f64, max: f64, default: f64 },
+}
+
+// `name` is the field path in `BaseChartConfig`, e.g. "mutation.angle"
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParameterSchema {
+    pub name: &'static str,
+    pub description: &'static str,
+    #[serde(flatten)]
+    pub range: ParameterRange,
+}
+
+// ranges are what a UI should offer; `ConfigValidator` decides what may actually be generated
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KindSchema {
+    #[serde(skip)]
+    pub kind: ChartKind,
+    pub name: String,
+    pub description: &'static str,
+    pub uses_mutation: bool,
+    pub uses_randomizer: bool,
+    pub parameters: Vec<ParameterSchema>,
+}
+
+impl KindSchema {
+    fn complexity(description: &'static str, min: u64, max: u64, default: u64) -> ParameterSchema {
+        ParameterSchema {
+            name: "complexity",
+            description,
+            range: ParameterRange::Integer { min, max, default },
+        }
+    }
+
+    fn randomness() -> Vec<ParameterSchema> {
+        let float = |name, description, max, default| ParameterSchema {
+            name,
+            description,
+            range: ParameterRange::Float {
+                min: 0.0,
+                max,
+                default,
+            },
+        };
+        let seed = |name, description| ParameterSchema {
+            name,
+            description,
+            range: ParameterRange::Integer {
+                min: 0,
+                max: u32::MAX as u64,
+                default: 0,
+            },
+        };
+        vec![
+            float(
+                "mutation.size",
+                "scales every segment length; 1 keeps the rule's proportions",
+                2.0,
+                1.0,
+            ),
+            float(
+                "mutation.angle",
+                "scales every fold or branch angle; 1 keeps the rule's angles",
+                2.0,
+                1.0,
+            ),
+            float(
+                "randomizer.size_amplitude",
+                "largest random amount added to the length scale",
+                1.0,
+                0.0,
+            ),
+            seed("randomizer.size_seed", "seed of the length randomizer"),
+            float(
+                "randomizer.angle_amplitude",
+                "largest random amount added to the angle scale",
+                1.0,
+                0.0,
+            ),
+            seed("randomizer.angle_seed", "seed of the angle randomizer"),
+        ]
+    }
+
+    fn float(
+        name: &'static str,
+        description: &'static str,
+        min: f64,
+        max: f64,
+        default: f64,
+    ) -> ParameterSchema {
+        ParameterSchema {
+            name,
+            description,
+            range: ParameterRange::Float { min, max, default },
+        }
+    }
+
+    fn integer(
+        name: &'static str,
+        description: &'static str,
+        max: u64,
+        default: u64,
+    ) -> ParameterSchema {
+        ParameterSchema {
+            name,
+            description,
+            range: ParameterRange::Integer {
+                min: 1,
+                max,
+                default,
+            },
+        }
+    }
+
+    // smooth kinds that can sample adaptively to a chord tolerance
+    fn is_sampled(kind: &ChartKind) -> bool {
+        matches!(
+            kind,
+            ChartKind::Clover
+                | ChartKind::Rose
+                | ChartKind::Hypotrochoid
+                | ChartKind::Epitrochoid
+                | ChartKind::Superformula
+        )
+    }
+
+    // the fields of the kind's `KindParameters` payload
+    fn kind_parameters(kind: &ChartKind) -> Vec<ParameterSchema> {
+        let mut parameters = match kind {
+            ChartKind::Cardioid
+            | ChartKind::Deltoid
+            | ChartKind::Nephroid
+            | ChartKind::Star
+            | ChartKind::Sunrise => vec![KindSchema::float(
+                "parameters.radius",
+                "radius of the circle the points lie on",
+                0.1,
+                10.0,
+                CircleParameters::default().radius,
+            )],
+            ChartKind::Clover => vec![ParameterSchema {
+                name: "parameters.density",
+                description: "points sampled per unit of complexity",
+                range: ParameterRange::Integer {
+                    min: 4,
+                    max: 200,
+                    default: CloverParameters::default().density,
+                },
+            }],
+            ChartKind::KochTriangleInner | ChartKind::KochTriangleOuter => {
+                vec![KindSchema::float(
+                    "parameters.offset",
+                    "distance from the centre to each side; other values than the default leave the sides apart, each drawn on its own",
+                    0.0,
+                    2.0,
+                    KochTriangleParameters::default().offset,
+                )]
+            }
+            ChartKind::Starmine => {
+                let defaults = StarmineParameters::default();
+                vec![
+                    KindSchema::float(
+                        "parameters.outer_radius",
+                        "radius of the spike tips",
+                        0.1,
+                        2.0,
+                        defaults.outer_radius,
+                    ),
+                    KindSchema::float(
+                        "parameters.inner_radius",
+                        "radius of the points between the spikes",
+                        0.0,
+                        2.0,
+                        defaults.inner_radius,
+                    ),
+                ]
+            }
+            ChartKind::Rose => {
+                let defaults = RoseParameters::default();
+                vec![
+                    KindSchema::integer(
+                        "parameters.numerator",
+                        "numerator n of the frequency n / d",
+                        20,
+                        defaults.numerator,
+                    ),
+                    KindSchema::integer(
+                        "parameters.denominator",
+                        "denominator d of the frequency n / d; the curve turns up to 2d times before closing",
+                        20,
+                        defaults.denominator,
+                    ),
+                ]
+            }
+            ChartKind::Hypotrochoid | ChartKind::Epitrochoid => {
+                let defaults = TrochoidParameters::default();
+                vec![
+                    KindSchema::integer(
+                        "parameters.fixed_radius",
+                        "radius of the fixed circle",
+                        20,
+                        defaults.fixed_radius,
+                    ),
+                    KindSchema::integer(
+                        "parameters.rolling_radius",
+                        "radius of the rolling circle",
+                        20,
+                        defaults.rolling_radius,
+                    ),
+                    KindSchema::float(
+                        "parameters.pen_distance",
+                        "distance of the pen from the rolling circle's centre",
+                        0.0,
+                        20.0,
+                        defaults.pen_distance,
+                    ),
+                ]
+            }
+            ChartKind::Superformula => {
+                let defaults = SuperformulaParameters::default();
+                vec![
+                    KindSchema::integer(
+                        "parameters.m_numerator",
+                        "numerator of the symmetry m",
+                        32,
+                        defaults.m_numerator,
+                    ),
+                    KindSchema::integer(
+                        "parameters.m_denominator",
+                        "denominator of the symmetry m",
+                        16,
+                        defaults.m_denominator,
+                    ),
+                    KindSchema::float("parameters.n1", "overall exponent", 0.1, 20.0, defaults.n1),
+                    KindSchema::float(
+                        "parameters.n2",
+                        "exponent of the cosine term",
+                        0.0,
+                        20.0,
+                        defaults.n2,
+                    ),
+                    KindSchema::float(
+                        "parameters.n3",
+                        "exponent of the sine term",
+                        0.0,
+                        20.0,
+                        defaults.n3,
+                    ),
+                    KindSchema::float(
+                        "parameters.a",
+                        "scale of the cosine term",
+                        0.1,
+                        4.0,
+                        defaults.a,
+                    ),
+                    KindSchema::float(
+                        "parameters.b",
+                        "scale of the sine term",
+                        0.1,
+                        4.0,
+                        defaults.b,
+                    ),
+                ]
+            }
+            _ => vec![],
+        };
+        if KindSchema::is_sampled(kind) {
+            parameters.push(KindSchema::float(
+                "parameters.tolerance",
+                "largest distance between the curve and its chords, sampling adaptively; 0 keeps the fixed sampling",
+                0.0,
+                0.1,
+                0.0,
+            ));
+        }
+        parameters
+    }
+
+    // `None` for kinds without a generator
+    pub fn describe(kind: &ChartKind) -> Option<KindSchema> {
+        let (description, complexity, randomized) = match kind {
+            ChartKind::BinaryTree => (
+                "tree whose branches split in two at every level",
+                KindSchema::complexity(
+                    "levels of branching",
+                    BinaryTree::MIN_COMPLEXITY,
+                    BinaryTree::MAX_COMPLEXITY,
+                    8,
+                ),
+                true,
+            ),
+            ChartKind::Cardioid | ChartKind::Deltoid | ChartKind::Nephroid => (
+                match kind {
+                    ChartKind::Cardioid => "times table of 2 on a circle, tracing a cardioid",
+                    ChartKind::Nephroid => "times table of 3 on a circle, tracing a nephroid",
+                    _ => "times table of -2 on a circle, tracing a deltoid",
+                },
+                KindSchema::complexity("number of points on the circle", 1, 2000, 200),
+                false,
+            ),
+            ChartKind::Clover => (
+                "rose curve with as many petals as the complexity when odd, twice as many when even",
+                KindSchema::complexity("frequency k of r = sin(k θ); sampled with `parameters.density` points per unit unless a tolerance is set", 1, 100, 5),
+                false,
+            ),
+            ChartKind::FoldCCurve | ChartKind::FoldDragon => (
+                match kind {
+                    ChartKind::FoldCCurve => "Lévy C curve, folding every segment the same way",
+                    _ => "dragon curve, folding segments alternately left and right",
+                },
+                KindSchema::complexity(
+                    "recursion depth starting at 2; segments double with each level",
+                    2,
+                    20,
+                    12,
+                ),
+                true,
+            ),
+            ChartKind::KochCurve | ChartKind::TriCis | ChartKind::TriTrans => (
+                match kind {
+                    ChartKind::KochCurve => "Koch curve with a triangular bump on every segment",
+                    ChartKind::TriCis => "square bumps folded to the same side",
+                    _ => "square bumps folded to alternate sides",
+                },
+                KindSchema::complexity(
+                    "recursion depth starting at 2; segments quadruple with each level",
+                    2,
+                    10,
+                    5,
+                ),
+                true,
+            ),
+            ChartKind::KochTriangleInner | ChartKind::KochTriangleOuter => (
+                match kind {
+                    ChartKind::KochTriangleInner => "Koch anti-snowflake with bumps facing inward",
+                    _ => "Koch snowflake with bumps facing outward",
+                },
+                KindSchema::complexity(
+                    "recursion depth of each side starting at 2; segments quadruple with each level",
+                    2,
+                    9,
+                    5,
+                ),
+                true,
+            ),
+            ChartKind::Star => (
+                "points on a circle, each linked to the point two positions ahead",
+                KindSchema::complexity("number of points on the circle", 1, 1000, 7),
+                false,
+            ),
+            ChartKind::Starmine => (
+                "star outline alternating between an outer and an inner radius",
+                KindSchema::complexity("number of spikes; two points each", 1, 500, 30),
+                false,
+            ),
+            ChartKind::Sunrise => (
+                "points on a circle, point i linked to point 2i + 2",
+                KindSchema::complexity("number of points on the circle", 1, 1000, 60),
+                false,
+            ),
+            ChartKind::Rose
+            | ChartKind::Hypotrochoid
+            | ChartKind::Epitrochoid
+            | ChartKind::Superformula => (
+                match kind {
+                    ChartKind::Rose => "rose curve r = cos(n / d θ), closing after every petal is drawn",
+                    ChartKind::Hypotrochoid => "spirograph curve of a circle rolling inside another",
+                    ChartKind::Epitrochoid => "spirograph curve of a circle rolling outside another",
+                    _ => "Gielis superformula, from polygons and stars to flower-like shapes",
+                },
+                KindSchema::complexity(
+                    "segments per full turn of the tangent; sharper bends get more points",
+                    1,
+                    360,
+                    72,
+                ),
+                false,
+            ),
+            ChartKind::Sunset => return None,
+        };
+        let mut parameters = vec![complexity];
+        if randomized {
+            parameters.extend(KindSchema::randomness());
+        }
+        parameters.extend(KindSchema::kind_parameters(kind));
+        Some(KindSchema {
+            kind: *kind,
+            name: format!("{:?}", kind),
+            description,
+            uses_mutation: randomized,
+            uses_randomizer: randomized,
+            parameters,
+        })
+    }
+
+    pub fn all() -> Vec<KindSchema> {
+        ChartKind::ALL
+            .iter()
+            .filter_map(KindSchema::describe)
+            .collect()
+    }
+
+    pub fn parameter(&self, name: &str) -> Option<&ParameterSchema> {
+        self.parameters.iter().find(|p| p.name == name)
+    }
+
+    pub fn default_config(&self) -> BaseChartConfig {
+        let integer = |name| match self.parameter(name).map(|p| &p.range) {
+            Some(ParameterRange::Integer { default, .. }) => *default,
+            _ => 0,
+        };
+        let float = |name| match self.parameter(name).map(|p| &p.range) {
+            Some(ParameterRange::Float { default, .. }) => *default,
+            _ => 0.0,
+        };
+        let tolerance = Some(float("parameters.tolerance")).filter(|tolerance| *tolerance > 0.0);
+        BaseChartConfig {
+            kind: self.kind,
+            complexity: integer("complexity"),
+            mutation: self.uses_mutation.then(|| Mutation {
+                size: float("mutation.size"),
+                angle: float("mutation.angle"),
+            }),
+            randomizer: self.uses_randomizer.then(|| Randomizer {
+                size_amplitude: float("randomizer.size_amplitude"),
+                size_seed: integer("randomizer.size_seed"),
+                angle_amplitude: float("randomizer.angle_amplitude"),
+                angle_seed: integer("randomizer.angle_seed"),
+            }),
+            parameters: match self.kind {
+                ChartKind::Cardioid
+                | ChartKind::Deltoid
+                | ChartKind::Nephroid
+                | ChartKind::Star
+                | ChartKind::Sunrise => Some(KindParameters::Circle(CircleParameters {
+                    radius: float("parameters.radius"),
+                })),
+                ChartKind::Clover => Some(KindParameters::Clover(CloverParameters {
+                    density: integer("parameters.density"),
+                    tolerance,
+                })),
+                ChartKind::KochTriangleInner | ChartKind::KochTriangleOuter => {
+                    Some(KindParameters::KochTriangle(KochTriangleParameters {
+                        offset: float("parameters.offset"),
+                    }))
+                }
+                ChartKind::Starmine => Some(KindParameters::Starmine(StarmineParameters {
+                    outer_radius: float("parameters.outer_radius"),
+                    inner_radius: float("parameters.inner_radius"),
+                })),
+                ChartKind::Rose => Some(KindParameters::Rose(RoseParameters {
+                    numerator: integer("parameters.numerator"),
+                    denominator: integer("parameters.denominator"),
+                    tolerance,
+                })),
+                ChartKind::Hypotrochoid | ChartKind::Epitrochoid => {
+                    Some(KindParameters::Trochoid(TrochoidParameters {
+                        fixed_radius: integer("parameters.fixed_radius"),
+                        rolling_radius: integer("parameters.rolling_radius"),
+                        pen_distance: float("parameters.pen_distance"),
+                        tolerance,
+                    }))
+                }
+                ChartKind::Superformula => {
+                    Some(KindParameters::Superformula(SuperformulaParameters {
+                        m_numerator: integer("parameters.m_numerator"),
+                        m_denominator: integer("parameters.m_denominator"),
+                        n1: float("parameters.n1"),
+                        n2: float("parameters.n2"),
+                        n3: float("parameters.n3"),
+                        a: float("parameters.a"),
+                        b: float("parameters.b"),
+                        tolerance,
+                    }))
+                }
+                _ => None,
+            },
+        }
+    }
+
+    pub fn to_json(schemas: &[KindSchema]) -> String {
+        serde_json::to_string_pretty(schemas).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::validation::{ConfigValidator, ValidationError};
+
+    #[test]
+    fn test_schemas_agree_with_validation() {
+        let schemas = KindSchema::all();
+        assert_eq!(ChartKind::ALL.len() - 1, schemas.len());
+        for schema in &schemas {
+            let default = schema.default_config();
+            assert!(
+                ConfigValidator::estimate(&default).is_ok(),
+                "{}",
+                schema.name
+            );
+
+            let Some(ParameterRange::Integer {
+                min,
+                max,
+                default: complexity,
+            }) = schema.parameter("complexity").map(|p| p.range.clone())
+            else {
+                panic!("{} has no complexity", schema.name);
+            };
+            assert!(min <= complexity && complexity <= max);
+            // the slider never leaves what can be generated, and stays under a million points
+            let at = |complexity| BaseChartConfig {
+                complexity,
+                ..default
+            };
+            assert!(ConfigValidator::estimate(&at(max)).unwrap().points <= 1 << 20);
+            // below the minimum is rejected, or clamped to the same chart
+            match ConfigValidator::estimate(&at(min - 1)) {
+                Err(ValidationError::ComplexityTooLow { .. }) => {}
+                below => assert_eq!(ConfigValidator::estimate(&at(min)), below),
+            }
+        }
+        assert_eq!(None, KindSchema::describe(&ChartKind::Sunset));
+    }
+
+    #[test]
+    fn test_json_description() {
+        let dragon = KindSchema::describe(&ChartKind::FoldDragon).unwrap();
+        let json = KindSchema::to_json(&[dragon]);
+        assert!(json.contains("\"name\": \"FoldDragon\""));
+        assert!(json.contains("\"uses_mutation\": true"));
+        assert!(json.contains(
+            "\"name\": \"complexity\",\n        \"description\": \"recursion depth starting at 2; segments double with each level\",\n        \"type\": \"integer\",\n        \"min\": 2,\n        \"max\": 20,\n        \"default\": 12"
+        ));
+        let star = KindSchema::describe(&ChartKind::Star).unwrap();
+        assert_eq!(2, star.parameters.len());
+        assert_eq!(
+            Some(KindParameters::Circle(CircleParameters { radius: 1.0 })),
+            star.default_config().parameters
+        );
+        assert_eq!(None, star.default_config().mutation);
+        let superformula = KindSchema::describe(&ChartKind::Superformula).unwrap();
+        assert_eq!(
+            Some(KindParameters::Superformula(
+                SuperformulaParameters::default()
+            )),
+            superformula.default_config().parameters
+        );
+    }
+}
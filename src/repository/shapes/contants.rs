@@ -1 +1,3 @@
 pub const BASE_AMPLITUDE: f64 = 0.1;
+// upper bound of points for order kinds whose cost grows with the square of the point count
+pub const QUADRATIC_ORDER_POINT_LIMIT: u64 = 4096;
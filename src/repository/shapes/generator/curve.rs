@@ -0,0 +1,174 @@
+use std::f64::consts::PI;
+
+use crate::model::shape::Point;
+
+pub trait ParametricCurve {
+    fn point(&self, t: f64) -> Point;
+    // smallest parameter span after which the curve retraces itself
+    fn period(&self) -> f64;
+    // oscillations per 2π of the parameter, e.g. k for r = sin(k θ); the starting grid scales with it
+    fn frequency(&self) -> f64 {
+        1.0
+    }
+}
+
+// when a segment is halved
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Refinement {
+    // the tangent turns by more than this many radians across it
+    Turn(f64),
+    // the curve at its middle strays further than this from its chord
+    ChordDeviation(f64),
+}
+
+impl Refinement {
+    // a tolerance of 0 is the same as none, leaving the kind's fixed sampling
+    pub fn chord_deviation(tolerance: Option<f64>) -> Option<Refinement> {
+        tolerance
+            .filter(|tolerance| *tolerance > 0.0)
+            .map(Refinement::ChordDeviation)
+    }
+}
+
+pub struct CurveSampler;
+
+impl CurveSampler {
+    // uniform start per oscillation, so features narrower than a segment are not skipped entirely
+    const SEGMENTS_PER_PERIOD: f64 = 16.0;
+    // bounds the halving at cusps, where the tangent turns by π however short the segment
+    const MAX_DEPTH: u32 = 10;
+
+    pub fn initial_segments(curve: &dyn ParametricCurve) -> u64 {
+        let oscillations = curve.period() / (2.0 * PI) * curve.frequency().max(1.0);
+        (oscillations * CurveSampler::SEGMENTS_PER_PERIOD).ceil() as u64
+    }
+
+    fn turn(start: &Point, middle: &Point, end: &Point) -> f64 {
+        let (ax, ay) = (middle.x - start.x, middle.y - start.y);
+        let (bx, by) = (end.x - middle.x, end.y - middle.y);
+        (ax * by - ay * bx).atan2(ax * bx + ay * by).abs()
+    }
+
+    fn deviation(start: &Point, middle: &Point, end: &Point) -> f64 {
+        let (cx, cy) = (end.x - start.x, end.y - start.y);
+        let (mx, my) = (middle.x - start.x, middle.y - start.y);
+        let chord = cx.hypot(cy);
+        if chord == 0.0 {
+            mx.hypot(my)
+        } else {
+            (cx * my - cy * mx).abs() / chord
+        }
+    }
+
+    fn exceeds(refinement: &Refinement, start: &Point, middle: &Point, end: &Point) -> bool {
+        match refinement {
+            Refinement::Turn(max_turn) => CurveSampler::turn(start, middle, end) > *max_turn,
+            Refinement::ChordDeviation(tolerance) => {
+                CurveSampler::deviation(start, middle, end) > *tolerance
+            }
+        }
+    }
+
+    // emits the points from `start` up to, but not including, `end`
+    fn refine(
+        curve: &dyn ParametricCurve,
+        refinement: &Refinement,
+        (t0, p0): (f64, Point),
+        (t1, p1): (f64, Point),
+        depth: u32,
+        emit: &mut dyn FnMut(Point),
+    ) {
+        let t = (t0 + t1) / 2.0;
+        let middle = curve.point(t);
+        if depth < CurveSampler::MAX_DEPTH && CurveSampler::exceeds(refinement, &p0, &middle, &p1) {
+            CurveSampler::refine(curve, refinement, (t0, p0), (t, middle), depth + 1, emit);
+            CurveSampler::refine(curve, refinement, (t, middle), (t1, p1), depth + 1, emit);
+        } else {
+            emit(p0);
+        }
+    }
+
+    fn walk(curve: &dyn ParametricCurve, refinement: Refinement, emit: &mut dyn FnMut(Point)) {
+        let period = curve.period();
+        let segments = CurveSampler::initial_segments(curve);
+        let mut start = (0.0, curve.point(0.0));
+        for i in 1..=segments {
+            let t = period * i as f64 / segments as f64;
+            let end = (t, curve.point(t));
+            CurveSampler::refine(curve, &refinement, start, end, 0, emit);
+            start = end;
+        }
+    }
+
+    // one period of the curve, its end left out so that a Loop order closes it;
+    // segments are halved until each of them meets `refinement`
+    pub fn sample(curve: &dyn ParametricCurve, refinement: Refinement) -> Vec<Point> {
+        let mut points: Vec<Point> = vec![];
+        CurveSampler::walk(curve, refinement, &mut |point| points.push(point));
+        points
+    }
+
+    // as many points as `sample` returns, without holding them
+    pub fn count(curve: &dyn ParametricCurve, refinement: Refinement) -> u64 {
+        let mut count = 0;
+        CurveSampler::walk(curve, refinement, &mut |_| count += 1);
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Ellipse {
+        radius_x: f64,
+    }
+
+    impl ParametricCurve for Ellipse {
+        fn point(&self, t: f64) -> Point {
+            Point {
+                x: self.radius_x * t.cos(),
+                y: t.sin(),
+            }
+        }
+
+        fn period(&self) -> f64 {
+            2.0 * PI
+        }
+    }
+
+    #[test]
+    fn test_sampling_follows_curvature() {
+        let circle = CurveSampler::sample(&Ellipse { radius_x: 1.0 }, Refinement::Turn(PI / 60.0));
+        assert_eq!(64, circle.len());
+        assert_eq!(Point { x: 1.0, y: 0.0 }, circle[0]);
+
+        // a flat ellipse bends sharply at its ends, which get the extra points
+        let flat = CurveSampler::sample(&Ellipse { radius_x: 4.0 }, Refinement::Turn(PI / 60.0));
+        let tip = flat.iter().filter(|p| p.x.abs() > 3.0).count();
+        let side = flat.iter().filter(|p| p.x.abs() < 1.0).count();
+        assert!(flat.len() > circle.len());
+        assert!(tip > 4 * side, "{} {}", tip, side);
+    }
+
+    #[test]
+    fn test_chord_deviation() {
+        // a chord spanning α bulges 1 - cos(α / 2) away from the unit circle
+        let circle = Ellipse { radius_x: 1.0 };
+        let points = CurveSampler::sample(&circle, Refinement::ChordDeviation(1e-3));
+        assert_eq!(128, points.len());
+        assert_eq!(
+            128,
+            CurveSampler::count(&circle, Refinement::ChordDeviation(1e-3))
+        );
+        let finer = CurveSampler::sample(&circle, Refinement::ChordDeviation(2.5e-4));
+        assert_eq!(256, finer.len());
+
+        // the nearly straight sides of a flat ellipse need fewer points than its ends, over a similar length
+        let flat =
+            CurveSampler::sample(&Ellipse { radius_x: 4.0 }, Refinement::ChordDeviation(1e-3));
+        let tip = flat.iter().filter(|p| p.x.abs() > 3.0).count();
+        let side = flat.iter().filter(|p| p.x.abs() < 1.0).count();
+        assert!(tip > 2 * side, "{} {}", tip, side);
+    }
+}
@@ -1,3 +1,5 @@
+pub mod curve;
 pub mod orders;
 pub mod points;
 pub mod randomizer;
+pub mod triangulation;
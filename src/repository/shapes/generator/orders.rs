@@ -1,10 +1,36 @@
-use crate::model::shape::Order;
+use super::triangulation::Triangulation;
+use crate::model::shape::{Order, Point};
+use crate::repository::shapes::contants::QUADRATIC_ORDER_POINT_LIMIT;
 
 pub enum OrderKind {
     Loop,
     StartEnd2xFaster,
     End2xFaster,
     Linear,
+    // links (start_multiplier * i + offset, end_multiplier * (i + step) + offset), both modulo the point count
+    TimesTable {
+        start_multiplier: f64,
+        end_multiplier: f64,
+        offset: f64,
+        step: f64,
+    },
+    // every pair of points; only the first QUADRATIC_ORDER_POINT_LIMIT points are linked, the rest are left out
+    Complete,
+    // star polygon {n/m}: links each point to the one `skip` positions ahead
+    StarPolygon {
+        skip: u64,
+    },
+    // links each of `points` to its `neighbours` closest ones; `point_count` is not used,
+    // and points past QUADRATIC_ORDER_POINT_LIMIT are left out
+    NearestNeighbours {
+        neighbours: u64,
+        points: Vec<Point>,
+    },
+    // edges of the Delaunay triangulation of `points`; `point_count` is not used,
+    // and points past QUADRATIC_ORDER_POINT_LIMIT are left out
+    Delaunay {
+        points: Vec<Point>,
+    },
 }
 
 pub struct OrderConfig {
@@ -23,6 +49,11 @@ pub struct OrdersGeneratorImpl;
 impl OrdersGenerator for OrdersGeneratorImpl {
     fn generate(config: OrderConfig) -> Vec<Order> {
         let OrderConfig { kind, point_count } = config;
+        // quadratic kinds only link the first points, up to the limit, rather than stall on large charts
+        let within = |mut points: Vec<Point>| {
+            points.truncate(QUADRATIC_ORDER_POINT_LIMIT as usize);
+            points
+        };
 
         match kind {
             OrderKind::Linear => (0..point_count - 1)
@@ -31,15 +62,78 @@ impl OrdersGenerator for OrdersGeneratorImpl {
             OrderKind::Loop => (0..point_count)
                 .map(|i| Order { link: (i, i + 1) })
                 .collect(),
-            OrderKind::StartEnd2xFaster => (0..point_count)
+            OrderKind::StartEnd2xFaster => OrdersGeneratorImpl::generate(OrderConfig {
+                kind: OrderKind::TimesTable {
+                    start_multiplier: 2.0,
+                    end_multiplier: 2.0,
+                    offset: 0.0,
+                    step: 1.0,
+                },
+                point_count,
+            }),
+            OrderKind::End2xFaster => OrdersGeneratorImpl::generate(OrderConfig {
+                kind: OrderKind::TimesTable {
+                    start_multiplier: 1.0,
+                    end_multiplier: 2.0,
+                    offset: 0.0,
+                    step: 1.0,
+                },
+                point_count,
+            }),
+            OrderKind::TimesTable {
+                start_multiplier,
+                end_multiplier,
+                offset,
+                step,
+            } => {
+                let wrap = |position: f64| {
+                    (position.rem_euclid(point_count as f64).round() as u64) % point_count
+                };
+                (0..point_count)
+                    .map(|i| Order {
+                        link: (
+                            wrap(start_multiplier * i as f64 + offset),
+                            wrap(end_multiplier * (i as f64 + step) + offset),
+                        ),
+                    })
+                    .collect()
+            }
+            OrderKind::Complete => {
+                let point_count = point_count.min(QUADRATIC_ORDER_POINT_LIMIT);
+                (0..point_count)
+                    .flat_map(|i| (i + 1..point_count).map(move |j| Order { link: (i, j) }))
+                    .collect()
+            }
+            OrderKind::StarPolygon { skip } => (0..point_count)
                 .map(|i| Order {
-                    link: ((2 * i) % point_count, (2 * (i + 1)) % point_count),
+                    link: (i, (i + skip) % point_count),
                 })
                 .collect(),
-            OrderKind::End2xFaster => (0..point_count)
-                .map(|i| Order {
-                    link: (i, (2 * (i + 1)) % point_count),
-                })
+            OrderKind::NearestNeighbours { neighbours, points } => {
+                let points = within(points);
+                let distance = |a: &Point, b: &Point| (a.x - b.x).powi(2) + (a.y - b.y).powi(2);
+                let mut links: Vec<(u64, u64)> = points
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, p)| {
+                        let mut others: Vec<usize> =
+                            (0..points.len()).filter(|&j| j != i).collect();
+                        others.sort_by(|&a, &b| {
+                            distance(p, &points[a]).total_cmp(&distance(p, &points[b]))
+                        });
+                        others
+                            .into_iter()
+                            .take(neighbours as usize)
+                            .map(move |j| (i.min(j) as u64, i.max(j) as u64))
+                    })
+                    .collect();
+                links.sort();
+                links.dedup();
+                links.into_iter().map(|link| Order { link }).collect()
+            }
+            OrderKind::Delaunay { points } => Triangulation::delaunay_edges(&within(points))
+                .into_iter()
+                .map(|link| Order { link })
                 .collect(),
         }
     }
@@ -161,4 +255,134 @@ mod tests {
 
         assert_eq!(expect, actual);
     }
+
+    #[test]
+    fn test_order_times_table_generation() {
+        let expect: Vec<Order> = vec![
+            Order { link: (0, 0) },
+            Order { link: (1, 3) },
+            Order { link: (2, 6) },
+            Order { link: (3, 2) },
+            Order { link: (4, 5) },
+            Order { link: (5, 1) },
+            Order { link: (6, 4) },
+        ];
+        let actual = OrdersGeneratorImpl::generate(OrderConfig {
+            kind: OrderKind::TimesTable {
+                start_multiplier: 1.0,
+                end_multiplier: 3.0,
+                offset: 0.0,
+                step: 0.0,
+            },
+            point_count: 7,
+        });
+
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn test_order_times_table_rational_generation() {
+        let expect: Vec<Order> = vec![
+            Order { link: (1, 1) },
+            Order { link: (2, 4) },
+            Order { link: (3, 0) },
+            Order { link: (4, 3) },
+            Order { link: (5, 5) },
+            Order { link: (0, 2) },
+        ];
+        let actual = OrdersGeneratorImpl::generate(OrderConfig {
+            kind: OrderKind::TimesTable {
+                start_multiplier: 1.0,
+                end_multiplier: 2.5,
+                offset: 1.0,
+                step: 0.0,
+            },
+            point_count: 6,
+        });
+
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn test_order_complete_generation() {
+        let expect: Vec<Order> = vec![
+            Order { link: (0, 1) },
+            Order { link: (0, 2) },
+            Order { link: (0, 3) },
+            Order { link: (1, 2) },
+            Order { link: (1, 3) },
+            Order { link: (2, 3) },
+        ];
+        let actual = OrdersGeneratorImpl::generate(OrderConfig {
+            kind: OrderKind::Complete,
+            point_count: 4,
+        });
+
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn test_order_star_polygon_generation() {
+        let expect: Vec<Order> = vec![
+            Order { link: (0, 3) },
+            Order { link: (1, 4) },
+            Order { link: (2, 5) },
+            Order { link: (3, 6) },
+            Order { link: (4, 0) },
+            Order { link: (5, 1) },
+            Order { link: (6, 2) },
+        ];
+        let actual = OrdersGeneratorImpl::generate(OrderConfig {
+            kind: OrderKind::StarPolygon { skip: 3 },
+            point_count: 7,
+        });
+
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn test_order_nearest_neighbours_generation() {
+        let points = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1.0, y: 0.0 },
+            Point { x: 3.0, y: 0.0 },
+            Point { x: 7.0, y: 0.0 },
+        ];
+        let expect: Vec<Order> = vec![
+            Order { link: (0, 1) },
+            Order { link: (1, 2) },
+            Order { link: (2, 3) },
+        ];
+        let actual = OrdersGeneratorImpl::generate(OrderConfig {
+            kind: OrderKind::NearestNeighbours {
+                neighbours: 1,
+                points,
+            },
+            point_count: 4,
+        });
+
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn test_order_quadratic_limit() {
+        // points past the limit are left unlinked, counted from the points given
+        let points: Vec<Point> = (0..QUADRATIC_ORDER_POINT_LIMIT + 8)
+            .map(|i| Point {
+                x: i as f64,
+                y: 0.0,
+            })
+            .collect();
+        let actual = OrdersGeneratorImpl::generate(OrderConfig {
+            kind: OrderKind::NearestNeighbours {
+                neighbours: 1,
+                points,
+            },
+            point_count: 4,
+        });
+        assert_eq!(QUADRATIC_ORDER_POINT_LIMIT as usize - 1, actual.len());
+        assert!(actual
+            .iter()
+            .all(|order| order.link.1 < QUADRATIC_ORDER_POINT_LIMIT));
+    }
 }
@@ -6,6 +6,37 @@ use rand::Rng;
 pub enum PointKind {
     Circle,
     Random,
+    Ellipse {
+        radius_x: f64,
+        radius_y: f64,
+    },
+    // regular polygon inscribed in the unit circle, points spread evenly along its edges;
+    // zero sides are taken as one
+    Polygon {
+        sides: u64,
+    },
+    ArchimedeanSpiral {
+        turns: f64,
+    },
+    // radius grows by `growth` per radian, scaled so that the outermost point is on the unit circle
+    LogarithmicSpiral {
+        turns: f64,
+        growth: f64,
+    },
+    Superellipse {
+        exponent: f64,
+        radius_x: f64,
+        radius_y: f64,
+    },
+    Lissajous {
+        frequency_x: f64,
+        frequency_y: f64,
+        phase: f64,
+    },
+    // row-major grid over [-1, 1] x [-1, 1], top row first; zero columns are taken as one
+    Grid {
+        columns: u64,
+    },
 }
 
 pub struct PointsConfig {
@@ -42,13 +73,116 @@ impl PointsGenerator for PointsGeneratorImpl {
                     y: rng.gen::<f64>() - 0.5,
                 })
                 .collect(),
+            PointKind::Ellipse { radius_x, radius_y } => (0..length)
+                .map(|i| {
+                    let radian = (2.0 * PI * i as f64) / length as f64;
+                    Point {
+                        x: radius_x * radian.sin(),
+                        y: radius_y * radian.cos(),
+                    }
+                })
+                .collect(),
+            PointKind::Polygon { sides } => {
+                let sides = sides.max(1);
+                let vertex = |k: u64| {
+                    let radian = (2.0 * PI * k as f64) / sides as f64;
+                    Point {
+                        x: radian.sin(),
+                        y: radian.cos(),
+                    }
+                };
+                (0..length)
+                    .map(|i| {
+                        let position = (sides * i) as f64 / length as f64;
+                        let edge = position.floor() as u64;
+                        let ratio = position - edge as f64;
+                        let start = vertex(edge);
+                        let end = vertex(edge + 1);
+                        Point {
+                            x: start.x + ratio * (end.x - start.x),
+                            y: start.y + ratio * (end.y - start.y),
+                        }
+                    })
+                    .collect()
+            }
+            PointKind::ArchimedeanSpiral { turns } => (0..length)
+                .map(|i| {
+                    let t = PointsGeneratorImpl::ratio(i, length);
+                    let radian = 2.0 * PI * turns * t;
+                    Point {
+                        x: t * radian.sin(),
+                        y: t * radian.cos(),
+                    }
+                })
+                .collect(),
+            PointKind::LogarithmicSpiral { turns, growth } => {
+                let max_radian = 2.0 * PI * turns;
+                (0..length)
+                    .map(|i| {
+                        let radian = max_radian * PointsGeneratorImpl::ratio(i, length);
+                        let radius = (growth * (radian - max_radian)).exp();
+                        Point {
+                            x: radius * radian.sin(),
+                            y: radius * radian.cos(),
+                        }
+                    })
+                    .collect()
+            }
+            PointKind::Superellipse {
+                exponent,
+                radius_x,
+                radius_y,
+            } => (0..length)
+                .map(|i| {
+                    let radian = (2.0 * PI * i as f64) / length as f64;
+                    let (sin, cos) = radian.sin_cos();
+                    Point {
+                        x: radius_x * sin.signum() * sin.abs().powf(2.0 / exponent),
+                        y: radius_y * cos.signum() * cos.abs().powf(2.0 / exponent),
+                    }
+                })
+                .collect(),
+            PointKind::Lissajous {
+                frequency_x,
+                frequency_y,
+                phase,
+            } => (0..length)
+                .map(|i| {
+                    let radian = (2.0 * PI * i as f64) / length as f64;
+                    Point {
+                        x: (frequency_x * radian + phase).sin(),
+                        y: (frequency_y * radian).sin(),
+                    }
+                })
+                .collect(),
+            PointKind::Grid { columns } => {
+                let columns = columns.max(1);
+                let rows = length.div_ceil(columns);
+                (0..length)
+                    .map(|i| Point {
+                        x: 2.0 * PointsGeneratorImpl::ratio(i % columns, columns) - 1.0,
+                        y: 1.0 - 2.0 * PointsGeneratorImpl::ratio(i / columns, rows),
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+impl PointsGeneratorImpl {
+    // position of `i` within `0..length`, reaching 1.0 at the last point
+    fn ratio(i: u64, length: u64) -> f64 {
+        if length <= 1 {
+            return 0.0;
         }
+        i as f64 / (length - 1) as f64
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::f64::consts::FRAC_1_SQRT_2;
 
     #[test]
     fn test_circle_generation() {
@@ -97,4 +231,202 @@ mod tests {
 
         assert_eq!(expect, actual);
     }
+
+    #[test]
+    fn test_ellipse_generation() {
+        let expect: Vec<Point> = PointsGeneratorImpl::generate(PointsConfig {
+            kind: PointKind::Circle,
+            length: 12,
+        })
+        .iter()
+        .map(|p| Point {
+            x: 2.0 * p.x,
+            y: 0.5 * p.y,
+        })
+        .collect();
+        let actual = PointsGeneratorImpl::generate(PointsConfig {
+            kind: PointKind::Ellipse {
+                radius_x: 2.0,
+                radius_y: 0.5,
+            },
+            length: 12,
+        });
+
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn test_polygon_generation() {
+        let expect = vec![
+            Point { x: 0.0, y: 1.0 },
+            Point { x: 0.5, y: 0.5 },
+            Point { x: 1.0, y: 0.0 },
+            Point { x: 0.5, y: -0.5 },
+            Point { x: 0.0, y: -1.0 },
+            Point { x: -0.5, y: -0.5 },
+            Point { x: -1.0, y: 0.0 },
+            Point { x: -0.5, y: 0.5 },
+        ];
+        let actual = PointsGeneratorImpl::generate(PointsConfig {
+            kind: PointKind::Polygon { sides: 4 },
+            length: 8,
+        });
+
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn test_archimedean_spiral_generation() {
+        let expect = vec![
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 0.5, y: 0.0 },
+            Point { x: 0.0, y: -1.0 },
+        ];
+        let actual = PointsGeneratorImpl::generate(PointsConfig {
+            kind: PointKind::ArchimedeanSpiral { turns: 0.5 },
+            length: 3,
+        });
+
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn test_logarithmic_spiral_generation() {
+        // the radius doubles over the half turn
+        let expect = vec![
+            Point { x: 0.0, y: 0.5 },
+            Point {
+                x: FRAC_1_SQRT_2,
+                y: 0.0,
+            },
+            Point { x: 0.0, y: -1.0 },
+        ];
+        let actual = PointsGeneratorImpl::generate(PointsConfig {
+            kind: PointKind::LogarithmicSpiral {
+                turns: 0.5,
+                growth: 2.0_f64.ln() / PI,
+            },
+            length: 3,
+        });
+
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn test_superellipse_generation() {
+        let ellipse = PointsGeneratorImpl::generate(PointsConfig {
+            kind: PointKind::Ellipse {
+                radius_x: 2.0,
+                radius_y: 0.5,
+            },
+            length: 12,
+        });
+        let actual = PointsGeneratorImpl::generate(PointsConfig {
+            kind: PointKind::Superellipse {
+                exponent: 2.0,
+                radius_x: 2.0,
+                radius_y: 0.5,
+            },
+            length: 12,
+        });
+        assert_eq!(ellipse, actual);
+
+        // higher exponents push the diagonals out towards the corners
+        let squircle = PointsGeneratorImpl::generate(PointsConfig {
+            kind: PointKind::Superellipse {
+                exponent: 4.0,
+                radius_x: 1.0,
+                radius_y: 1.0,
+            },
+            length: 8,
+        });
+        assert_eq!(
+            Point {
+                x: 0.8408964152537145,
+                y: 0.8408964152537145,
+            },
+            squircle[1]
+        );
+        assert_eq!(
+            Point {
+                x: -0.8408964152537145,
+                y: -0.8408964152537145,
+            },
+            squircle[5]
+        );
+    }
+
+    #[test]
+    fn test_lissajous_generation() {
+        let expect = vec![
+            Point { x: 1.0, y: 0.0 },
+            Point {
+                x: FRAC_1_SQRT_2,
+                y: 1.0,
+            },
+            Point { x: 0.0, y: 0.0 },
+            Point {
+                x: -FRAC_1_SQRT_2,
+                y: -1.0,
+            },
+            Point { x: -1.0, y: 0.0 },
+            Point {
+                x: -FRAC_1_SQRT_2,
+                y: 1.0,
+            },
+            Point { x: 0.0, y: 0.0 },
+            Point {
+                x: FRAC_1_SQRT_2,
+                y: -1.0,
+            },
+        ];
+        let actual = PointsGeneratorImpl::generate(PointsConfig {
+            kind: PointKind::Lissajous {
+                frequency_x: 1.0,
+                frequency_y: 2.0,
+                phase: PI / 2.0,
+            },
+            length: 8,
+        });
+
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn test_degenerate_generation() {
+        let grid = PointsGeneratorImpl::generate(PointsConfig {
+            kind: PointKind::Grid { columns: 0 },
+            length: 3,
+        });
+        assert_eq!(
+            vec![
+                Point { x: -1.0, y: 1.0 },
+                Point { x: -1.0, y: 0.0 },
+                Point { x: -1.0, y: -1.0 },
+            ],
+            grid
+        );
+        let polygon = PointsGeneratorImpl::generate(PointsConfig {
+            kind: PointKind::Polygon { sides: 0 },
+            length: 4,
+        });
+        assert!(polygon.iter().all(|p| p.x.is_finite() && p.y.is_finite()));
+    }
+
+    #[test]
+    fn test_grid_generation() {
+        let expect = vec![
+            Point { x: -1.0, y: 1.0 },
+            Point { x: 0.0, y: 1.0 },
+            Point { x: 1.0, y: 1.0 },
+            Point { x: -1.0, y: -1.0 },
+            Point { x: 0.0, y: -1.0 },
+        ];
+        let actual = PointsGeneratorImpl::generate(PointsConfig {
+            kind: PointKind::Grid { columns: 3 },
+            length: 5,
+        });
+
+        assert_eq!(expect, actual);
+    }
 }
@@ -0,0 +1,133 @@
+use crate::model::shape::Point;
+
+pub struct Triangulation;
+
+impl Triangulation {
+    fn orientation(a: &Point, b: &Point, c: &Point) -> f64 {
+        (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+    }
+
+    // positive when `d` lies inside the circumcircle of the counter-clockwise triangle (a, b, c)
+    fn in_circle(a: &Point, b: &Point, c: &Point, d: &Point) -> f64 {
+        let (adx, ady) = (a.x - d.x, a.y - d.y);
+        let (bdx, bdy) = (b.x - d.x, b.y - d.y);
+        let (cdx, cdy) = (c.x - d.x, c.y - d.y);
+        let ad = adx * adx + ady * ady;
+        let bd = bdx * bdx + bdy * bdy;
+        let cd = cdx * cdx + cdy * cdy;
+        adx * (bdy * cd - bd * cdy) - ady * (bdx * cd - bd * cdx) + ad * (bdx * cdy - bdy * cdx)
+    }
+
+    fn counter_clockwise(vertices: &[Point], triangle: [usize; 3]) -> [usize; 3] {
+        let [a, b, c] = triangle;
+        if Triangulation::orientation(&vertices[a], &vertices[b], &vertices[c]) < 0.0 {
+            [a, c, b]
+        } else {
+            triangle
+        }
+    }
+
+    // Bowyer-Watson; returns the sorted, deduplicated edges of the Delaunay triangulation
+    pub fn delaunay_edges(points: &[Point]) -> Vec<(u64, u64)> {
+        if points.len() < 2 {
+            return vec![];
+        }
+        let (mut min_x, mut min_y) = (f64::INFINITY, f64::INFINITY);
+        let (mut max_x, mut max_y) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for p in points {
+            min_x = min_x.min(p.x);
+            min_y = min_y.min(p.y);
+            max_x = max_x.max(p.x);
+            max_y = max_y.max(p.y);
+        }
+        let span = (max_x - min_x).max(max_y - min_y).max(1.0);
+        let center = Point {
+            x: (min_x + max_x) / 2.0,
+            y: (min_y + max_y) / 2.0,
+        };
+
+        let count = points.len();
+        let mut vertices: Vec<Point> = points.to_vec();
+        vertices.push(Point {
+            x: center.x - 20.0 * span,
+            y: center.y - span,
+        });
+        vertices.push(Point {
+            x: center.x,
+            y: center.y + 20.0 * span,
+        });
+        vertices.push(Point {
+            x: center.x + 20.0 * span,
+            y: center.y - span,
+        });
+        let mut triangles: Vec<[usize; 3]> = vec![Triangulation::counter_clockwise(
+            &vertices,
+            [count, count + 1, count + 2],
+        )];
+
+        for i in 0..count {
+            let p = &vertices[i];
+            let (bad, good): (Vec<[usize; 3]>, Vec<[usize; 3]>) =
+                triangles.into_iter().partition(|&[a, b, c]| {
+                    Triangulation::in_circle(&vertices[a], &vertices[b], &vertices[c], p) > 0.0
+                });
+            let edges: Vec<(usize, usize)> = bad
+                .iter()
+                .flat_map(|&[a, b, c]| [(a, b), (b, c), (c, a)])
+                .collect();
+            let boundary = edges.iter().filter(|&&(a, b)| {
+                edges
+                    .iter()
+                    .filter(|&&(c, d)| (a == c && b == d) || (a == d && b == c))
+                    .count()
+                    == 1
+            });
+            triangles = good;
+            for &(a, b) in boundary {
+                triangles.push(Triangulation::counter_clockwise(&vertices, [a, b, i]));
+            }
+        }
+
+        let mut edges: Vec<(u64, u64)> = triangles
+            .iter()
+            .filter(|triangle| triangle.iter().all(|&v| v < count))
+            .flat_map(|&[a, b, c]| [(a, b), (b, c), (c, a)])
+            .map(|(a, b)| (a.min(b) as u64, a.max(b) as u64))
+            .collect();
+        edges.sort();
+        edges.dedup();
+        edges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delaunay_edges_generation() {
+        let points = vec![
+            Point { x: 0.0, y: 1.0 },
+            Point { x: -1.0, y: -1.0 },
+            Point { x: 1.0, y: -1.0 },
+            Point { x: 0.0, y: 0.0 },
+        ];
+        let actual = Triangulation::delaunay_edges(&points);
+        let expect: Vec<(u64, u64)> = vec![(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)];
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn test_delaunay_edges_flip() {
+        // the short diagonal (1, 3) is chosen over (0, 2)
+        let points = vec![
+            Point { x: -2.0, y: 0.0 },
+            Point { x: 0.0, y: 0.5 },
+            Point { x: 2.0, y: 0.0 },
+            Point { x: 0.0, y: -0.5 },
+        ];
+        let actual = Triangulation::delaunay_edges(&points);
+        let expect: Vec<(u64, u64)> = vec![(0, 1), (0, 3), (1, 2), (1, 3), (2, 3)];
+        assert_eq!(expect, actual);
+    }
+}
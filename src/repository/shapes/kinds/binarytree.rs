@@ -2,7 +2,9 @@ use std::cmp::{self, Ordering};
 
 use super::traits::ShapesGenerator;
 use crate::{
-    model::shape::{BaseChartConfig, Mutation, Order, Point},
+    model::shape::{
+        AttributedChart, BaseChartConfig, Mutation, Order, OrderAttributes, Point, PointAttributes,
+    },
     repository::shapes::generator::randomizer::RandomGenerator,
 };
 
@@ -14,8 +16,15 @@ struct IndexedPoint {
 pub struct BinaryTree;
 
 impl BinaryTree {
-    fn point_count(complexity: u64) -> u64 {
-        let limited_compelixty: u32 = cmp::max(cmp::min(complexity as u32, 10), 2);
+    // complexities outside this range are clamped to it
+    pub const MIN_COMPLEXITY: u64 = 2;
+    pub const MAX_COMPLEXITY: u64 = 10;
+
+    pub fn point_count(complexity: u64) -> u64 {
+        let limited_compelixty = cmp::max(
+            cmp::min(complexity, BinaryTree::MAX_COMPLEXITY),
+            BinaryTree::MIN_COMPLEXITY,
+        ) as u32;
         2_u64.pow(limited_compelixty + 1)
     }
 
@@ -139,6 +148,40 @@ impl ShapesGenerator for BinaryTree {
             .collect();
         orders
     }
+
+    // point `i` is a node of a binary heap: its children are `2 * i` and `2 * i + 1`, and order `i` draws node `i + 1`
+    fn generate_attributed_chart(&self, config: BaseChartConfig) -> AttributedChart {
+        let chart = self.generate_chart(config);
+        let level = |index: u64| {
+            if index == 0 {
+                0
+            } else {
+                index.ilog2() as u64 + 1
+            }
+        };
+        let point_attributes = (0..chart.points.len() as u64)
+            .map(|index| PointAttributes {
+                depth: level(index),
+                parent: index.checked_sub(1).map(|_| index / 2),
+            })
+            .collect();
+        let order_attributes = (0..chart.orders.len() as u64)
+            .map(|division| {
+                let node = division + 1;
+                OrderAttributes {
+                    depth: level(node),
+                    division,
+                    parent: (node / 2).checked_sub(1),
+                    rule: if node < 2 { None } else { Some(node % 2) },
+                }
+            })
+            .collect();
+        AttributedChart {
+            chart,
+            point_attributes,
+            order_attributes,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -163,6 +206,7 @@ mod tests {
                 angle_amplitude: 0.0,
                 angle_seed: 0,
             }),
+            parameters: None,
         });
         let expect: Vec<Point> = vec![
             Point { x: 0.0, y: -1.0 },
@@ -250,4 +294,59 @@ mod tests {
         ];
         assert_eq!(expect, actual);
     }
+
+    #[test]
+    fn test_attributes_generation() {
+        let chart = BinaryTree {};
+        let actual = chart.generate_attributed_chart(BaseChartConfig {
+            kind: ChartKind::BinaryTree,
+            complexity: 2,
+            mutation: Some(Mutation {
+                size: 1.0,
+                angle: 1.0,
+            }),
+            randomizer: Some(Randomizer {
+                size_amplitude: 0.0,
+                size_seed: 0,
+                angle_amplitude: 0.0,
+                angle_seed: 0,
+            }),
+            parameters: None,
+        });
+        assert_eq!(
+            vec![0, 1, 2, 2, 3, 3, 3, 3],
+            actual
+                .point_attributes
+                .iter()
+                .map(|p| p.depth)
+                .collect::<Vec<u64>>()
+        );
+        assert_eq!(
+            vec![
+                None,
+                Some(0),
+                Some(1),
+                Some(1),
+                Some(2),
+                Some(2),
+                Some(3),
+                Some(3)
+            ],
+            actual
+                .point_attributes
+                .iter()
+                .map(|p| p.parent)
+                .collect::<Vec<Option<u64>>>()
+        );
+        assert_eq!(
+            OrderAttributes {
+                depth: 3,
+                division: 4,
+                parent: Some(1),
+                rule: Some(1),
+            },
+            actual.order_attributes[4]
+        );
+        assert_eq!(None, actual.order_attributes[0].parent);
+    }
 }
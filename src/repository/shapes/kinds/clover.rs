@@ -1,22 +1,68 @@
 use std::f64::consts::PI;
 
 use super::traits::ShapesGenerator;
-use crate::model::shape::{BaseChartConfig, Order, Point};
+use crate::model::shape::{BaseChartConfig, Chart, ChartKind, CloverParameters, Order, Point};
+use crate::repository::shapes::generator::curve::{CurveSampler, ParametricCurve, Refinement};
 use crate::repository::shapes::generator::orders::{
     OrderConfig, OrderKind, OrdersGenerator, OrdersGeneratorImpl,
 };
 
+struct CloverCurve {
+    frequency: u64,
+}
+
+impl ParametricCurve for CloverCurve {
+    fn point(&self, t: f64) -> Point {
+        let amplitude = (self.frequency as f64 * t).sin();
+        Point {
+            x: amplitude * (t - PI).cos(),
+            y: amplitude * (t - PI).sin(),
+        }
+    }
+
+    // odd frequencies trace every petal within half a turn
+    fn period(&self) -> f64 {
+        if self.frequency % 2 == 1 {
+            PI
+        } else {
+            2.0 * PI
+        }
+    }
+
+    fn frequency(&self) -> f64 {
+        self.frequency as f64
+    }
+}
+
 pub struct Clover;
 
 impl Clover {
-    fn point_count(complexity: u64) -> u64 {
-        return 40 * complexity;
+    pub fn curve(complexity: u64) -> impl ParametricCurve {
+        CloverCurve {
+            frequency: complexity,
+        }
+    }
+
+    fn point_count(complexity: u64, density: u64) -> u64 {
+        return density * complexity;
+    }
+
+    fn orders(point_count: u64) -> Vec<Order> {
+        OrdersGeneratorImpl::generate(OrderConfig {
+            kind: OrderKind::Loop,
+            point_count,
+        })
     }
 }
 
 impl ShapesGenerator for Clover {
     fn generate_base_points(self: &Self, config: BaseChartConfig) -> Vec<Point> {
-        let point_count = Clover::point_count(config.complexity);
+        let CloverParameters { density, tolerance } = config.clover_parameters();
+        if let Some(refinement) = Refinement::chord_deviation(tolerance) {
+            let curve = Clover::curve(config.complexity);
+            return CurveSampler::sample(&curve, refinement);
+        }
+        let point_count = Clover::point_count(config.complexity, density);
         (0..point_count)
             .map(|i| {
                 let angle = (2.0 * PI * i as f64) / point_count as f64;
@@ -29,18 +75,30 @@ impl ShapesGenerator for Clover {
             .collect()
     }
 
+    // the orders of the default chart; `generate_chart` follows the configured sampling
     fn generate_orders(self: &Self, complexity: u64) -> Vec<Order> {
-        let point_count = Clover::point_count(complexity);
-        return OrdersGeneratorImpl::generate(OrderConfig {
-            kind: OrderKind::Loop,
-            point_count,
-        });
+        self.generate_chart(BaseChartConfig {
+            kind: ChartKind::Clover,
+            complexity,
+            mutation: None,
+            randomizer: None,
+            parameters: None,
+        })
+        .orders
+    }
+
+    fn generate_chart(&self, config: BaseChartConfig) -> Chart {
+        let points = self.generate_base_points(config);
+        Chart {
+            orders: Clover::orders(points.len() as u64),
+            points,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::model::shape::ChartKind;
+    use crate::model::shape::KindParameters;
 
     use super::*;
 
@@ -52,6 +110,7 @@ mod tests {
             kind: ChartKind::Clover,
             mutation: None,
             randomizer: None,
+            parameters: None,
         });
         let expect = &vec![
             Point { x: -0.0, y: -0.0 },
@@ -90,4 +149,61 @@ mod tests {
         let expect: Vec<Order> = (0..3 * 40).map(|i| Order { link: (i, i + 1) }).collect();
         assert_eq!(expect, actual);
     }
+
+    #[test]
+    fn test_density() {
+        let chart = Clover {};
+        let config = |parameters| BaseChartConfig {
+            complexity: 3,
+            kind: ChartKind::Clover,
+            mutation: None,
+            randomizer: None,
+            parameters,
+        };
+        let defaults = Some(KindParameters::Clover(CloverParameters::default()));
+        assert_eq!(
+            chart.generate_chart(config(None)),
+            chart.generate_chart(config(defaults))
+        );
+
+        let sparse = chart.generate_chart(config(Some(KindParameters::Clover(CloverParameters {
+            density: 8,
+            tolerance: None,
+        }))));
+        assert_eq!(24, sparse.points.len());
+        assert_eq!(24, sparse.orders.len());
+        assert_eq!(Order { link: (23, 24) }, sparse.orders[23]);
+        // the orders alone only follow the default density, so they do not fit these points
+        assert_eq!(120, chart.generate_orders(3).len());
+        assert_ne!(sparse.orders, chart.generate_orders(3));
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let chart = Clover {};
+        let config = |complexity, tolerance| BaseChartConfig {
+            complexity,
+            kind: ChartKind::Clover,
+            mutation: None,
+            randomizer: None,
+            parameters: Some(KindParameters::Clover(CloverParameters {
+                density: 40,
+                tolerance: Some(tolerance),
+            })),
+        };
+        let coarse = chart.generate_chart(config(40, 1e-3));
+        let fine = chart.generate_chart(config(40, 1e-4));
+        assert_eq!(coarse.points.len(), coarse.orders.len());
+        assert!(coarse.points.len() < fine.points.len());
+        // every petal tip is reached even at high complexity
+        let reach = coarse
+            .points
+            .iter()
+            .map(|p| p.x.hypot(p.y))
+            .fold(0.0, f64::max);
+        assert!(reach > 1.0 - 1e-3);
+
+        assert_eq!(PI, CloverCurve { frequency: 5 }.period());
+        assert_eq!(2.0 * PI, CloverCurve { frequency: 6 }.period());
+    }
 }
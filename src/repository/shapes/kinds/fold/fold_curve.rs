@@ -1,5 +1,5 @@
 use crate::{
-    model::shape::{BaseChartConfig, ChartKind, Order, Point},
+    model::shape::{AttributedChart, BaseChartConfig, ChartKind, Order, Point},
     repository::shapes::{
         generator::orders::{OrderConfig, OrderKind, OrdersGenerator, OrdersGeneratorImpl},
         kinds::traits::ShapesGenerator,
@@ -64,11 +64,17 @@ impl ShapesGenerator for FoldCurve {
             point_count,
         })
     }
+
+    fn generate_attributed_chart(&self, config: BaseChartConfig) -> AttributedChart {
+        let rules = Self::select_fold_rules(&config.kind);
+        let orders = self.generate_orders(config.complexity);
+        FoldCurveGenerator::generate_attributed_chart(config, rules, orders)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::model::shape::{ChartKind, Mutation, Randomizer};
+    use crate::model::shape::{ChartKind, Mutation, OrderAttributes, Randomizer};
 
     use super::*;
 
@@ -88,6 +94,7 @@ mod tests {
                 angle_amplitude: 0.0,
                 angle_seed: 0,
             }),
+            parameters: None,
         });
         let expect: Vec<Point> = vec![
             Point { x: -1.0, y: 0.0 },
@@ -143,6 +150,7 @@ mod tests {
                 angle_amplitude: 0.0,
                 angle_seed: 0,
             }),
+            parameters: None,
         });
         let expect: Vec<Point> = vec![
             Point { x: -1.0, y: 0.0 },
@@ -198,4 +206,44 @@ mod tests {
         ];
         assert_eq!(expect, actual);
     }
+
+    #[test]
+    fn test_dragon_curve_attributes() {
+        let chart = FoldCurve {};
+        let config = || BaseChartConfig {
+            kind: ChartKind::FoldDragon,
+            complexity: 4,
+            mutation: Some(Mutation {
+                size: 1.0,
+                angle: 1.0,
+            }),
+            randomizer: Some(Randomizer {
+                size_amplitude: 0.0,
+                size_seed: 0,
+                angle_amplitude: 0.0,
+                angle_seed: 0,
+            }),
+            parameters: None,
+        };
+        let actual = chart.generate_attributed_chart(config());
+
+        assert_eq!(chart.generate_chart(config()), actual.chart);
+        assert_eq!(
+            vec![1, 4, 3, 4, 2, 4, 3, 4, 1],
+            actual
+                .point_attributes
+                .iter()
+                .map(|p| p.depth)
+                .collect::<Vec<u64>>()
+        );
+        let expect: Vec<OrderAttributes> = (0..8)
+            .map(|division| OrderAttributes {
+                depth: 4,
+                division,
+                parent: Some(division / 2),
+                rule: Some(division / 2 % 2),
+            })
+            .collect();
+        assert_eq!(expect, actual.order_attributes);
+    }
 }
@@ -1,10 +1,28 @@
 use crate::{
-    model::shape::{BaseChartConfig, Mutation, Point},
+    model::shape::{
+        AttributedChart, BaseChartConfig, Chart, Mutation, Order, OrderAttributes, Point,
+        PointAttributes,
+    },
     repository::shapes::generator::randomizer::RandomGenerator,
 };
 
 use super::models::{Fold, FoldRule};
 
+struct FoldAttributes {
+    points: Vec<PointAttributes>,
+    orders: Vec<OrderAttributes>,
+}
+
+// what stays the same, or keeps drawing, across the whole recursion
+struct FoldState<'a> {
+    complexity: u64,
+    mutation: Mutation,
+    rules: &'a [FoldRule],
+    length_randomizer: RandomGenerator,
+    angle_randomizer: RandomGenerator,
+    attributes: Option<&'a mut FoldAttributes>,
+}
+
 pub struct FoldCurveGenerator;
 
 impl FoldCurveGenerator {
@@ -52,27 +70,46 @@ impl FoldCurveGenerator {
     }
 
     fn recursive(
-        complexity: u64,
-        mutation: &Mutation,
+        state: &mut FoldState,
         div: u64,
         depth: u64,
         start: &Point,
         end: &Point,
-        rules: &Vec<FoldRule>,
-        length_randomizer: &mut RandomGenerator,
-        angle_randomizer: &mut RandomGenerator,
+        ends: (PointAttributes, PointAttributes),
     ) -> Vec<Point> {
-        let index = (div % rules.len() as u64) as usize;
-        let rule = &rules[index];
+        let index = (div % state.rules.len() as u64) as usize;
+        let rule = &state.rules[index];
         let mut div_points = FoldCurveGenerator::get_div_points(
-            &mutation,
+            &state.mutation,
             &start,
             &end,
             rule,
-            length_randomizer,
-            angle_randomizer,
+            &mut state.length_randomizer,
+            &mut state.angle_randomizer,
         );
-        if depth == complexity {
+        let fold_len = rule.folds.len() as u64;
+        let div_attributes = PointAttributes {
+            depth,
+            parent: Some(div),
+        };
+        if depth == state.complexity {
+            if let Some(attributes) = state.attributes.as_deref_mut() {
+                if div == 0 {
+                    attributes.points.push(ends.0);
+                }
+                attributes
+                    .points
+                    .extend(div_points.iter().map(|_| div_attributes));
+                attributes.points.push(ends.1);
+                attributes
+                    .orders
+                    .extend((0..fold_len + 1).map(|i| OrderAttributes {
+                        depth,
+                        division: (fold_len + 1) * div + i,
+                        parent: Some(div),
+                        rule: Some(index as u64),
+                    }));
+            }
             let mut points: Vec<Point> = vec![];
             if div == 0 {
                 points.push(Point {
@@ -84,6 +121,9 @@ impl FoldCurveGenerator {
             points.push(Point { x: end.x, y: end.y });
             return points;
         }
+        let mut vertices: Vec<PointAttributes> = vec![ends.0];
+        vertices.extend(div_points.iter().map(|_| div_attributes));
+        vertices.push(ends.1);
         let mut points: Vec<Point> = vec![];
         points.push(Point {
             x: start.x,
@@ -91,50 +131,72 @@ impl FoldCurveGenerator {
         });
         points.append(&mut div_points);
         points.push(Point { x: end.x, y: end.y });
-        let fold_len = rule.folds.len() as u64;
         (0..points.len() - 1)
             .map(|i| (&points[i], &points[i + 1]))
             .enumerate()
             .flat_map(|(i, (div_start, div_end))| {
                 let next_div = (fold_len + 1) * div + i as u64;
                 FoldCurveGenerator::recursive(
-                    complexity,
-                    &mutation,
+                    state,
                     next_div,
                     depth + 1,
                     div_start,
                     div_end,
-                    &rules,
-                    length_randomizer,
-                    angle_randomizer,
+                    (vertices[i], vertices[i + 1]),
                 )
             })
             .collect()
     }
 
-    pub fn generate_points(config: BaseChartConfig, rules: Vec<FoldRule>) -> Vec<Point> {
+    fn generate(
+        config: BaseChartConfig,
+        rules: Vec<FoldRule>,
+        attributes: Option<&mut FoldAttributes>,
+    ) -> Vec<Point> {
         let randomizer = config.randomizer.unwrap();
-        let complexity = config.complexity;
-        let mutation = config.mutation.unwrap();
-
-        let mut length_randomizer =
-            RandomGenerator::new(randomizer.size_seed, randomizer.size_amplitude);
-        let mut angle_randomizer =
-            RandomGenerator::new(randomizer.angle_seed, randomizer.angle_amplitude);
+        let mut state = FoldState {
+            complexity: config.complexity,
+            mutation: config.mutation.unwrap(),
+            rules: &rules,
+            length_randomizer: RandomGenerator::new(
+                randomizer.size_seed,
+                randomizer.size_amplitude,
+            ),
+            angle_randomizer: RandomGenerator::new(
+                randomizer.angle_seed,
+                randomizer.angle_amplitude,
+            ),
+            attributes,
+        };
         let start = Point { x: -1.0, y: 0.0 };
         let end = Point { x: 1.0, y: 0.0 };
+        let initial = PointAttributes {
+            depth: 1,
+            parent: None,
+        };
 
-        FoldCurveGenerator::recursive(
-            complexity,
-            &mutation,
-            0,
-            2,
-            &start,
-            &end,
-            &rules,
-            &mut length_randomizer,
-            &mut angle_randomizer,
-        )
+        FoldCurveGenerator::recursive(&mut state, 0, 2, &start, &end, (initial, initial))
+    }
+
+    pub fn generate_points(config: BaseChartConfig, rules: Vec<FoldRule>) -> Vec<Point> {
+        FoldCurveGenerator::generate(config, rules, None)
+    }
+
+    pub fn generate_attributed_chart(
+        config: BaseChartConfig,
+        rules: Vec<FoldRule>,
+        orders: Vec<Order>,
+    ) -> AttributedChart {
+        let mut attributes = FoldAttributes {
+            points: vec![],
+            orders: vec![],
+        };
+        let points = FoldCurveGenerator::generate(config, rules, Some(&mut attributes));
+        AttributedChart {
+            chart: Chart { points, orders },
+            point_attributes: attributes.points,
+            order_attributes: attributes.orders,
+        }
     }
 }
 
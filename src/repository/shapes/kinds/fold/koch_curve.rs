@@ -1,5 +1,5 @@
 use crate::{
-    model::shape::{BaseChartConfig, Order, Point},
+    model::shape::{AttributedChart, BaseChartConfig, Order, Point},
     repository::shapes::{
         generator::orders::{OrderConfig, OrderKind, OrdersGenerator, OrdersGeneratorImpl},
         kinds::traits::ShapesGenerator,
@@ -53,6 +53,12 @@ impl ShapesGenerator for KochCurve {
             point_count,
         })
     }
+
+    fn generate_attributed_chart(&self, config: BaseChartConfig) -> AttributedChart {
+        let rules = Self::get_fold_rule();
+        let orders = self.generate_orders(config.complexity);
+        FoldCurveGenerator::generate_attributed_chart(config, rules, orders)
+    }
 }
 
 #[cfg(test)]
@@ -77,6 +83,7 @@ mod tests {
                 angle_amplitude: 0.0,
                 angle_seed: 0,
             }),
+            parameters: None,
         });
         let expect: Vec<Point> = vec![
             Point { x: -1.0, y: 0.0 },
@@ -1,70 +1,79 @@
 use crate::{
-    model::shape::{BaseChartConfig, ChartKind, Order, Point},
-    repository::shapes::{
-        generator::orders::{OrderConfig, OrderKind, OrdersGenerator, OrdersGeneratorImpl},
-        kinds::traits::ShapesGenerator,
+    model::{
+        shape::{AttributedChart, BaseChartConfig, Chart, ChartKind, Order, Point},
+        transform::Transform,
     },
+    repository::shapes::kinds::traits::ShapesGenerator,
 };
 
 use super::koch_curve::KochCurve;
 
 pub struct KochTriangle;
 
-impl ShapesGenerator for KochTriangle {
-    fn generate_base_points(self: &Self, config: BaseChartConfig) -> Vec<Point> {
-        let is_inner = match config.kind {
+impl KochTriangle {
+    fn is_inner(kind: &ChartKind) -> bool {
+        match kind {
             ChartKind::KochTriangleInner => true,
             ChartKind::KochTriangleOuter => false,
             _ => {
                 panic!("Unsupported chart kind!");
             }
-        };
-        let chart = KochCurve {};
-        let mut base_points = chart.generate_base_points(config);
-        let radian = 120.0_f64.to_radians();
+        }
+    }
 
+    // every side keeps its own orders, so no chord is drawn between sides that do not meet
+    fn assemble(side: Chart, is_inner: bool, offset: f64) -> Chart {
+        let mut placement = Transform::translate(0.0, offset);
         if is_inner {
-            base_points = base_points
-                .iter()
-                .map(|p| Point { x: p.x, y: -p.y })
-                .collect();
+            placement = Transform::reflect(0.0).then(&placement);
         }
-        base_points = base_points
-            .iter()
-            .map(|p| Point {
-                x: p.x,
-                y: p.y + 1.0 / 3.0_f64.sqrt(),
-            })
-            .collect();
+        let side = Chart {
+            points: placement.apply_points(&side.points),
+            orders: side.orders,
+        };
 
-        let mut points: Vec<Point> = vec![];
-        let origin = Point { x: 0.0, y: 0.0 };
-        let mut base_points_120 = base_points
-            .iter()
-            .map(|p| origin.rotate_by(p, radian))
-            .collect();
-        let mut base_points_240 = base_points
-            .iter()
-            .map(|p| origin.rotate_by(p, 2.0 * radian))
-            .collect();
-        points.append(&mut base_points);
-        points.append(&mut base_points_120);
-        points.append(&mut base_points_240);
-        points
+        side.replicate(&Transform::rotations(3))
+    }
+}
+
+impl ShapesGenerator for KochTriangle {
+    fn generate_base_points(self: &Self, config: BaseChartConfig) -> Vec<Point> {
+        self.generate_chart(config).points
     }
 
+    // the orders do not depend on where the side's points lie
     fn generate_orders(self: &Self, complexity: u64) -> Vec<Order> {
-        let point_count = 3 * (2_u64.pow(2 * (complexity as u32 - 1)) + 1);
-        OrdersGeneratorImpl::generate(OrderConfig {
-            kind: OrderKind::Linear,
-            point_count,
-        })
+        let orders = KochCurve {}.generate_orders(complexity);
+        let side = Chart {
+            points: vec![Point { x: 0.0, y: 0.0 }; orders.len() + 1],
+            orders,
+        };
+        KochTriangle::assemble(side, false, 0.0).orders
+    }
+
+    fn generate_chart(&self, config: BaseChartConfig) -> Chart {
+        let is_inner = KochTriangle::is_inner(&config.kind);
+        let offset = config.koch_triangle_parameters().offset;
+        KochTriangle::assemble(KochCurve {}.generate_chart(config), is_inner, offset)
+    }
+
+    fn generate_attributed_chart(&self, config: BaseChartConfig) -> AttributedChart {
+        let is_inner = KochTriangle::is_inner(&config.kind);
+        let offset = config.koch_triangle_parameters().offset;
+        let side = KochCurve {}.generate_attributed_chart(config);
+        AttributedChart {
+            point_attributes: side.point_attributes.repeat(3),
+            order_attributes: side.order_attributes.repeat(3),
+            chart: KochTriangle::assemble(side.chart, is_inner, offset),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::model::shape::{ChartKind, Mutation, Randomizer};
+    use crate::model::shape::{
+        ChartKind, KindParameters, KochTriangleParameters, Mutation, Randomizer,
+    };
 
     use super::*;
 
@@ -84,6 +93,7 @@ mod tests {
                 angle_amplitude: 0.0,
                 angle_seed: 0,
             }),
+            parameters: None,
         });
         let expect: Vec<Point> = vec![
             Point {
@@ -289,6 +299,7 @@ mod tests {
     fn test_orders_generation() {
         let chart = KochTriangle {};
         let actual = chart.generate_orders(3);
+        // three sides of 17 points, none linked to the next
         let expect: Vec<Order> = vec![
             Order { link: (0, 1) },
             Order { link: (1, 2) },
@@ -306,7 +317,6 @@ mod tests {
             Order { link: (13, 14) },
             Order { link: (14, 15) },
             Order { link: (15, 16) },
-            Order { link: (16, 17) },
             Order { link: (17, 18) },
             Order { link: (18, 19) },
             Order { link: (19, 20) },
@@ -323,7 +333,6 @@ mod tests {
             Order { link: (30, 31) },
             Order { link: (31, 32) },
             Order { link: (32, 33) },
-            Order { link: (33, 34) },
             Order { link: (34, 35) },
             Order { link: (35, 36) },
             Order { link: (36, 37) },
@@ -343,4 +352,57 @@ mod tests {
         ];
         assert_eq!(expect, actual);
     }
+
+    #[test]
+    fn test_replicated_chart() {
+        let config = |kind| BaseChartConfig {
+            kind,
+            complexity: 3,
+            mutation: Some(Mutation {
+                size: 1.0,
+                angle: 1.0,
+            }),
+            randomizer: Some(Randomizer {
+                size_amplitude: 0.0,
+                size_seed: 0,
+                angle_amplitude: 0.0,
+                angle_seed: 0,
+            }),
+            parameters: None,
+        };
+        let chart = KochTriangle {}.generate_chart(config(ChartKind::KochTriangleOuter));
+        assert_eq!(51, chart.points.len());
+        assert_eq!(KochTriangle {}.generate_orders(3), chart.orders);
+        let attributed =
+            KochTriangle {}.generate_attributed_chart(config(ChartKind::KochTriangleOuter));
+        assert_eq!(chart, attributed.chart);
+        assert_eq!(chart.orders.len(), attributed.order_attributes.len());
+    }
+
+    #[test]
+    fn test_offset_keeps_sides_apart() {
+        let chart = KochTriangle {}.generate_chart(BaseChartConfig {
+            kind: ChartKind::KochTriangleInner,
+            complexity: 3,
+            mutation: Some(Mutation {
+                size: 1.0,
+                angle: 1.0,
+            }),
+            randomizer: Some(Randomizer {
+                size_amplitude: 0.0,
+                size_seed: 0,
+                angle_amplitude: 0.0,
+                angle_seed: 0,
+            }),
+            parameters: Some(KindParameters::KochTriangle(KochTriangleParameters {
+                offset: 1.0,
+            })),
+        });
+        // the sides no longer meet, and no order bridges the gap between them
+        assert!(chart.points[16] != chart.points[17]);
+        assert!(chart
+            .orders
+            .iter()
+            .all(|order| order.link.0 / 17 == order.link.1 / 17));
+    }
 }
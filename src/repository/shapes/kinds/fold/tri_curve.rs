@@ -1,5 +1,5 @@
 use crate::{
-    model::shape::{BaseChartConfig, ChartKind, Order, Point},
+    model::shape::{AttributedChart, BaseChartConfig, ChartKind, Order, Point},
     repository::shapes::{
         generator::orders::{OrderConfig, OrderKind, OrdersGenerator, OrdersGeneratorImpl},
         kinds::traits::ShapesGenerator,
@@ -83,6 +83,12 @@ impl ShapesGenerator for TriCurve {
             point_count,
         })
     }
+
+    fn generate_attributed_chart(&self, config: BaseChartConfig) -> AttributedChart {
+        let rules = Self::select_fold_rule(&config.kind);
+        let orders = self.generate_orders(config.complexity);
+        FoldCurveGenerator::generate_attributed_chart(config, rules, orders)
+    }
 }
 
 #[cfg(test)]
@@ -107,6 +113,7 @@ mod tests {
                 angle_amplitude: 0.0,
                 angle_seed: 0,
             }),
+            parameters: None,
         });
         let expect: Vec<Point> = vec![
             Point { x: -1.0, y: 0.0 },
@@ -194,6 +201,7 @@ mod tests {
                 angle_amplitude: 0.0,
                 angle_seed: 0,
             }),
+            parameters: None,
         });
         let expect: Vec<Point> = vec![
             Point { x: -1.0, y: 0.0 },
@@ -1,7 +1,11 @@
 pub mod binarytree;
 pub mod clover;
 pub mod fold;
+pub mod parametric;
+pub mod selector;
+pub mod spatial;
 pub mod star;
 pub mod starmine;
 pub mod sunrise;
+pub mod times_table;
 pub mod traits;
@@ -0,0 +1,369 @@
+use std::f64::consts::PI;
+
+use super::traits::ShapesGenerator;
+use crate::model::shape::{
+    BaseChartConfig, Chart, ChartKind, Order, Point, RoseParameters, SuperformulaParameters,
+    TrochoidParameters,
+};
+use crate::repository::shapes::generator::curve::{CurveSampler, ParametricCurve, Refinement};
+use crate::repository::shapes::generator::orders::{
+    OrderConfig, OrderKind, OrdersGenerator, OrdersGeneratorImpl,
+};
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+struct RoseCurve {
+    parameters: RoseParameters,
+}
+
+impl ParametricCurve for RoseCurve {
+    fn point(&self, t: f64) -> Point {
+        let k = self.parameters.numerator as f64 / self.parameters.denominator as f64;
+        let r = (k * t).cos();
+        Point {
+            x: r * t.cos(),
+            y: r * t.sin(),
+        }
+    }
+
+    // with n / d in lowest terms the petals repeat after π d when both are odd, 2π d otherwise
+    fn period(&self) -> f64 {
+        let RoseParameters {
+            numerator,
+            denominator,
+            ..
+        } = self.parameters;
+        let divisor = gcd(numerator, denominator);
+        let (n, d) = (numerator / divisor, denominator / divisor);
+        if n % 2 == 1 && d % 2 == 1 {
+            PI * d as f64
+        } else {
+            2.0 * PI * d as f64
+        }
+    }
+
+    fn frequency(&self) -> f64 {
+        self.parameters.numerator as f64 / self.parameters.denominator as f64
+    }
+}
+
+struct TrochoidCurve {
+    parameters: TrochoidParameters,
+    inside: bool,
+}
+
+impl ParametricCurve for TrochoidCurve {
+    // scaled so that the curve stays within the unit circle
+    fn point(&self, t: f64) -> Point {
+        let TrochoidParameters {
+            fixed_radius,
+            rolling_radius,
+            pen_distance,
+            ..
+        } = self.parameters;
+        let (fixed, rolling) = (fixed_radius as f64, rolling_radius as f64);
+        let centre = if self.inside {
+            fixed - rolling
+        } else {
+            fixed + rolling
+        };
+        let extent = centre.abs() + pen_distance;
+        let scale = if extent > 0.0 { 1.0 / extent } else { 1.0 };
+        let k = centre / rolling * t;
+        let pen = if self.inside {
+            Point {
+                x: pen_distance * k.cos(),
+                y: -pen_distance * k.sin(),
+            }
+        } else {
+            Point {
+                x: -pen_distance * k.cos(),
+                y: -pen_distance * k.sin(),
+            }
+        };
+        Point {
+            x: scale * (centre * t.cos() + pen.x),
+            y: scale * (centre * t.sin() + pen.y),
+        }
+    }
+
+    // the rolling circle is back at its starting contact after rolling_radius / gcd turns
+    fn period(&self) -> f64 {
+        let TrochoidParameters {
+            fixed_radius,
+            rolling_radius,
+            ..
+        } = self.parameters;
+        2.0 * PI * (rolling_radius / gcd(fixed_radius, rolling_radius)) as f64
+    }
+
+    // the pen circles the rolling centre (R ∓ r) / r times per turn
+    fn frequency(&self) -> f64 {
+        let TrochoidParameters {
+            fixed_radius,
+            rolling_radius,
+            ..
+        } = self.parameters;
+        let (fixed, rolling) = (fixed_radius as f64, rolling_radius as f64);
+        let centre = if self.inside {
+            fixed - rolling
+        } else {
+            fixed + rolling
+        };
+        (centre / rolling).abs()
+    }
+}
+
+struct SuperformulaCurve {
+    parameters: SuperformulaParameters,
+}
+
+impl ParametricCurve for SuperformulaCurve {
+    fn point(&self, t: f64) -> Point {
+        let SuperformulaParameters {
+            m_numerator,
+            m_denominator,
+            n1,
+            n2,
+            n3,
+            a,
+            b,
+            ..
+        } = self.parameters;
+        let angle = m_numerator as f64 / m_denominator as f64 * t / 4.0;
+        let r =
+            ((angle.cos() / a).abs().powf(n2) + (angle.sin() / b).abs().powf(n3)).powf(-1.0 / n1);
+        Point {
+            x: r * t.cos(),
+            y: r * t.sin(),
+        }
+    }
+
+    // the radius repeats every 4π q / p for m = p / q, the angle every 2π
+    fn period(&self) -> f64 {
+        let SuperformulaParameters {
+            m_numerator,
+            m_denominator,
+            ..
+        } = self.parameters;
+        let divisor = gcd(m_numerator, m_denominator);
+        let (p, q) = (m_numerator / divisor, m_denominator / divisor);
+        2.0 * PI * (2 * q / gcd(p, 2 * q)) as f64
+    }
+
+    fn frequency(&self) -> f64 {
+        self.parameters.m_numerator as f64 / self.parameters.m_denominator as f64 / 2.0
+    }
+}
+
+pub struct Parametric {
+    pub kind: ChartKind,
+}
+
+impl Parametric {
+    pub fn curve(config: &BaseChartConfig) -> Box<dyn ParametricCurve> {
+        match config.kind {
+            ChartKind::Rose => Box::new(RoseCurve {
+                parameters: config.rose_parameters(),
+            }),
+            ChartKind::Hypotrochoid | ChartKind::Epitrochoid => Box::new(TrochoidCurve {
+                parameters: config.trochoid_parameters(),
+                inside: config.kind == ChartKind::Hypotrochoid,
+            }),
+            ChartKind::Superformula => Box::new(SuperformulaCurve {
+                parameters: config.superformula_parameters(),
+            }),
+            _ => panic!("Unsupported chart kind!"),
+        }
+    }
+
+    // complexity is the number of segments per full turn of the tangent, unless the kind's
+    // parameters set a chord tolerance
+    pub fn refinement(config: &BaseChartConfig) -> Refinement {
+        let tolerance = match config.kind {
+            ChartKind::Rose => config.rose_parameters().tolerance,
+            ChartKind::Hypotrochoid | ChartKind::Epitrochoid => {
+                config.trochoid_parameters().tolerance
+            }
+            ChartKind::Superformula => config.superformula_parameters().tolerance,
+            _ => None,
+        };
+        Refinement::chord_deviation(tolerance)
+            .unwrap_or(Refinement::Turn(2.0 * PI / config.complexity as f64))
+    }
+
+    fn orders(point_count: u64) -> Vec<Order> {
+        OrdersGeneratorImpl::generate(OrderConfig {
+            kind: OrderKind::Loop,
+            point_count,
+        })
+    }
+}
+
+impl ShapesGenerator for Parametric {
+    fn generate_base_points(&self, config: BaseChartConfig) -> Vec<Point> {
+        let points = CurveSampler::sample(
+            Parametric::curve(&config).as_ref(),
+            Parametric::refinement(&config),
+        );
+        if config.kind != ChartKind::Superformula {
+            return points;
+        }
+        // the superformula has no closed-form extent, so it is fitted to the unit circle after sampling;
+        // its tolerance therefore applies to the unfitted curve
+        let extent = points.iter().map(|p| p.x.hypot(p.y)).fold(0.0, f64::max);
+        let scale = if extent > 0.0 { 1.0 / extent } else { 1.0 };
+        points
+            .iter()
+            .map(|p| Point {
+                x: scale * p.x,
+                y: scale * p.y,
+            })
+            .collect()
+    }
+
+    // the orders of the default chart, as the point count depends on the curve;
+    // `generate_chart` follows the configured parameters
+    fn generate_orders(&self, complexity: u64) -> Vec<Order> {
+        let points = self.generate_base_points(BaseChartConfig {
+            kind: self.kind,
+            complexity,
+            mutation: None,
+            randomizer: None,
+            parameters: None,
+        });
+        Parametric::orders(points.len() as u64)
+    }
+
+    fn generate_chart(&self, config: BaseChartConfig) -> Chart {
+        let points = self.generate_base_points(config);
+        Chart {
+            orders: Parametric::orders(points.len() as u64),
+            points,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::shape::KindParameters;
+
+    fn config(kind: ChartKind, parameters: Option<KindParameters>) -> BaseChartConfig {
+        BaseChartConfig {
+            kind,
+            complexity: 64,
+            mutation: None,
+            randomizer: None,
+            parameters,
+        }
+    }
+
+    #[test]
+    fn test_closed_periods() {
+        let rose = |numerator, denominator| {
+            Parametric::curve(&config(
+                ChartKind::Rose,
+                Some(KindParameters::Rose(RoseParameters {
+                    numerator,
+                    denominator,
+                    tolerance: None,
+                })),
+            ))
+            .period()
+        };
+        assert_eq!(PI, rose(3, 1));
+        assert_eq!(2.0 * PI, rose(2, 1));
+        assert_eq!(3.0 * PI, rose(3, 9));
+        assert_eq!(6.0 * PI, rose(6, 9));
+        assert_eq!(8.0 * PI, rose(5, 4));
+
+        let hypotrochoid = Parametric::curve(&config(ChartKind::Hypotrochoid, None));
+        assert_eq!(6.0 * PI, hypotrochoid.period());
+        let superformula = |m_numerator, m_denominator| {
+            Parametric::curve(&config(
+                ChartKind::Superformula,
+                Some(KindParameters::Superformula(SuperformulaParameters {
+                    m_numerator,
+                    m_denominator,
+                    ..SuperformulaParameters::default()
+                })),
+            ))
+            .period()
+        };
+        assert_eq!(2.0 * PI, superformula(6, 1));
+        assert_eq!(4.0 * PI, superformula(5, 1));
+        assert_eq!(6.0 * PI, superformula(2, 3));
+
+        // the sample after the last lands back on the first point
+        for kind in [
+            ChartKind::Rose,
+            ChartKind::Hypotrochoid,
+            ChartKind::Epitrochoid,
+            ChartKind::Superformula,
+        ] {
+            let curve = Parametric::curve(&config(kind, None));
+            let start = curve.point(0.0);
+            let end = curve.point(curve.period());
+            assert!((start.x - end.x).abs() < 1e-9 && (start.y - end.y).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_chart_generation() {
+        for kind in [
+            ChartKind::Rose,
+            ChartKind::Hypotrochoid,
+            ChartKind::Epitrochoid,
+            ChartKind::Superformula,
+        ] {
+            let generator = Parametric { kind };
+            let chart = generator.generate_chart(config(kind, None));
+            assert_eq!(chart.points.len(), chart.orders.len());
+            assert_eq!(generator.generate_orders(64), chart.orders);
+            assert!(chart.points.iter().all(|p| p.x.hypot(p.y) <= 1.0 + 1e-9));
+            // finer turns add points
+            let coarse = generator.generate_chart(BaseChartConfig {
+                complexity: 16,
+                ..config(kind, None)
+            });
+            assert!(coarse.points.len() < chart.points.len());
+        }
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let generator = Parametric {
+            kind: ChartKind::Rose,
+        };
+        let rose = |complexity, tolerance| {
+            generator.generate_chart(BaseChartConfig {
+                complexity,
+                ..config(
+                    ChartKind::Rose,
+                    Some(KindParameters::Rose(RoseParameters {
+                        tolerance,
+                        ..RoseParameters::default()
+                    })),
+                )
+            })
+        };
+        // a tolerance replaces the turn limit, so complexity no longer matters
+        assert_eq!(rose(16, Some(1e-3)), rose(64, Some(1e-3)));
+        assert!(rose(16, Some(1e-3)).points.len() < rose(16, Some(1e-4)).points.len());
+        assert_eq!(
+            generator.generate_chart(config(ChartKind::Rose, None)),
+            rose(64, None)
+        );
+        assert_eq!(rose(64, Some(0.0)), rose(64, None));
+        // the orders alone only follow the default sampling, so they do not fit these points
+        assert_eq!(rose(64, None).orders, generator.generate_orders(64));
+        assert_ne!(rose(64, Some(1e-3)).orders, generator.generate_orders(64));
+    }
+}
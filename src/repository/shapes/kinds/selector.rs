@@ -0,0 +1,79 @@
+use super::{
+    binarytree::BinaryTree,
+    clover::Clover,
+    fold::{
+        fold_curve::FoldCurve, koch_curve::KochCurve, koch_triangle::KochTriangle,
+        tri_curve::TriCurve,
+    },
+    parametric::Parametric,
+    spatial::{binarytree::SpatialBinaryTree, hilbert::HilbertCurve, menger::MengerSponge},
+    star::Star,
+    starmine::Starmine,
+    sunrise::Sunrise,
+    times_table::TimesTable,
+    traits::{ShapesGenerator, SpatialShapesGenerator},
+};
+use crate::model::shape::{ChartKind, SpatialChartKind};
+
+pub struct ShapesGeneratorSelector;
+
+impl ShapesGeneratorSelector {
+    pub fn select(kind: &ChartKind) -> Box<dyn ShapesGenerator> {
+        match kind {
+            ChartKind::BinaryTree => Box::new(BinaryTree {}),
+            ChartKind::Cardioid | ChartKind::Deltoid | ChartKind::Nephroid => {
+                Box::new(TimesTable { kind: *kind })
+            }
+            ChartKind::Clover => Box::new(Clover {}),
+            ChartKind::FoldCCurve | ChartKind::FoldDragon => Box::new(FoldCurve {}),
+            ChartKind::KochCurve => Box::new(KochCurve {}),
+            ChartKind::KochTriangleInner | ChartKind::KochTriangleOuter => {
+                Box::new(KochTriangle {})
+            }
+            ChartKind::Star => Box::new(Star {}),
+            ChartKind::Starmine => Box::new(Starmine {}),
+            ChartKind::Sunrise => Box::new(Sunrise {}),
+            ChartKind::TriCis | ChartKind::TriTrans => Box::new(TriCurve {}),
+            ChartKind::Rose
+            | ChartKind::Hypotrochoid
+            | ChartKind::Epitrochoid
+            | ChartKind::Superformula => Box::new(Parametric { kind: *kind }),
+            ChartKind::Sunset => panic!("Unsupported chart kind!"),
+        }
+    }
+
+    pub fn select_spatial(kind: &SpatialChartKind) -> Box<dyn SpatialShapesGenerator> {
+        match kind {
+            SpatialChartKind::BinaryTree => Box::new(SpatialBinaryTree {}),
+            SpatialChartKind::HilbertCurve => Box::new(HilbertCurve {}),
+            SpatialChartKind::MengerSponge => Box::new(MengerSponge {}),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::shape::{BaseChartConfig, Order};
+
+    #[test]
+    fn test_chart_generation() {
+        let chart =
+            ShapesGeneratorSelector::select(&ChartKind::Nephroid).generate_chart(BaseChartConfig {
+                kind: ChartKind::Nephroid,
+                complexity: 3,
+                mutation: None,
+                randomizer: None,
+                parameters: None,
+            });
+        assert_eq!(3, chart.points.len());
+        assert_eq!(
+            vec![
+                Order { link: (0, 0) },
+                Order { link: (1, 0) },
+                Order { link: (2, 0) },
+            ],
+            chart.orders
+        );
+    }
+}
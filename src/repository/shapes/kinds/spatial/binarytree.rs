@@ -0,0 +1,251 @@
+use std::cmp::Ordering;
+
+use crate::{
+    model::shape::{Mutation, Order, Point3, SpatialChartConfig},
+    repository::shapes::{
+        generator::randomizer::RandomGenerator,
+        kinds::{
+            binarytree::BinaryTree,
+            traits::{ShapesGenerator, SpatialShapesGenerator},
+        },
+    },
+};
+
+struct Branch {
+    index: u64,
+    point: Point3,
+    // axis the children pitch around
+    normal: Point3,
+}
+
+// `BinaryTree` whose branching plane turns by a yaw angle at every level
+pub struct SpatialBinaryTree;
+
+impl SpatialBinaryTree {
+    fn add(a: &Point3, b: &Point3) -> Point3 {
+        Point3 {
+            x: a.x + b.x,
+            y: a.y + b.y,
+            z: a.z + b.z,
+        }
+    }
+
+    // Rodrigues' rotation of `vector` about the unit `axis`
+    fn rotate(vector: &Point3, axis: &Point3, radian: f64) -> Point3 {
+        let (sin, cos) = radian.sin_cos();
+        let dot = vector.x * axis.x + vector.y * axis.y + vector.z * axis.z;
+        let cross = Point3 {
+            x: axis.y * vector.z - axis.z * vector.y,
+            y: axis.z * vector.x - axis.x * vector.z,
+            z: axis.x * vector.y - axis.y * vector.x,
+        };
+        Point3 {
+            x: vector.x * cos + cross.x * sin + axis.x * dot * (1.0 - cos),
+            y: vector.y * cos + cross.y * sin + axis.y * dot * (1.0 - cos),
+            z: vector.z * cos + cross.z * sin + axis.z * dot * (1.0 - cos),
+        }
+    }
+
+    fn unit(vector: &Point3) -> Point3 {
+        let length = (vector.x.powi(2) + vector.y.powi(2) + vector.z.powi(2)).sqrt();
+        Point3 {
+            x: vector.x / length,
+            y: vector.y / length,
+            z: vector.z / length,
+        }
+    }
+
+    // draws the randomizers in the same order as `BinaryTree`, so a zero yaw reproduces it in the xy plane
+    #[allow(clippy::too_many_arguments)]
+    fn divide_base_points(
+        depth_threshold: u64,
+        mutation: &Mutation,
+        yaw: f64,
+        start: &Branch,
+        end: &Branch,
+        depth: u64,
+        parent_length: f64,
+        parent_angle: f64,
+        length_randomizer: &mut RandomGenerator,
+        angle_randomizer: &mut RandomGenerator,
+    ) -> Vec<Branch> {
+        let length_random = length_randomizer.generate();
+        let angle_random = angle_randomizer.generate();
+        let length = parent_length * (length_random + mutation.size);
+        let angle = parent_angle * (angle_random + mutation.angle);
+        if depth >= depth_threshold {
+            return vec![];
+        }
+        let vector = Point3 {
+            x: length * (end.point.x - start.point.x),
+            y: length * (end.point.y - start.point.y),
+            z: length * (end.point.z - start.point.z),
+        };
+
+        let mut children: Vec<Branch> = [(2 * depth, angle), (2 * depth + 1, -angle)]
+            .iter()
+            .map(|&(index, pitch)| {
+                let branch = SpatialBinaryTree::rotate(&vector, &end.normal, pitch);
+                Branch {
+                    index,
+                    point: SpatialBinaryTree::add(&end.point, &branch),
+                    normal: SpatialBinaryTree::rotate(
+                        &end.normal,
+                        &SpatialBinaryTree::unit(&branch),
+                        yaw,
+                    ),
+                }
+            })
+            .collect();
+        let mut middle: Vec<Branch> = vec![];
+        for child in &children {
+            middle.append(&mut SpatialBinaryTree::divide_base_points(
+                depth_threshold,
+                mutation,
+                yaw,
+                end,
+                child,
+                child.index,
+                length,
+                angle,
+                length_randomizer,
+                angle_randomizer,
+            ));
+        }
+        children.append(&mut middle);
+        children
+    }
+
+    fn generate(config: SpatialChartConfig, yaw: f64) -> Vec<Point3> {
+        let randomizer = config.randomizer.unwrap();
+        let mutation = config.mutation.unwrap();
+        let normal = Point3 {
+            x: 0.0,
+            y: 0.0,
+            z: 1.0,
+        };
+
+        let mut points: Vec<Branch> = vec![
+            Branch {
+                index: 0,
+                point: Point3 {
+                    x: 0.0,
+                    y: -1.0,
+                    z: 0.0,
+                },
+                normal,
+            },
+            Branch {
+                index: 1,
+                point: Point3 {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+                normal,
+            },
+        ];
+        let mut size_randomizer =
+            RandomGenerator::new(randomizer.size_seed, randomizer.size_amplitude);
+        let mut angle_randomizer =
+            RandomGenerator::new(randomizer.angle_seed, randomizer.angle_amplitude);
+
+        let depth_threshold = BinaryTree::point_count(config.complexity) / 2;
+        points.append(&mut SpatialBinaryTree::divide_base_points(
+            depth_threshold,
+            &mutation,
+            yaw,
+            &points[0],
+            &points[1],
+            1,
+            0.85,
+            45.0_f64.to_radians(),
+            &mut size_randomizer,
+            &mut angle_randomizer,
+        ));
+        points.sort_by(|a, b| {
+            if a.index > b.index {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            }
+        });
+        points.iter().map(|branch| branch.point).collect()
+    }
+}
+
+impl SpatialShapesGenerator for SpatialBinaryTree {
+    fn generate_base_points(&self, config: SpatialChartConfig) -> Vec<Point3> {
+        SpatialBinaryTree::generate(config, config.tree_parameters().yaw)
+    }
+
+    fn generate_orders(&self, complexity: u64) -> Vec<Order> {
+        BinaryTree {}.generate_orders(complexity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::shape::{
+        BaseChartConfig, ChartKind, Randomizer, SpatialChartKind, SpatialKindParameters,
+        SpatialTreeParameters,
+    };
+
+    fn config(complexity: u64) -> SpatialChartConfig {
+        SpatialChartConfig {
+            kind: SpatialChartKind::BinaryTree,
+            complexity,
+            mutation: Some(Mutation {
+                size: 1.0,
+                angle: 1.0,
+            }),
+            randomizer: Some(Randomizer {
+                size_amplitude: 0.2,
+                size_seed: 3,
+                angle_amplitude: 0.1,
+                angle_seed: 4,
+            }),
+            parameters: None,
+        }
+    }
+
+    #[test]
+    fn test_flat_tree_matches_binary_tree() {
+        let config = config(4);
+        let flat: Vec<Point3> = SpatialBinaryTree::generate(config, 0.0);
+        let expect = BinaryTree {}.generate_base_points(BaseChartConfig {
+            kind: ChartKind::BinaryTree,
+            complexity: config.complexity,
+            mutation: config.mutation,
+            randomizer: config.randomizer,
+            parameters: None,
+        });
+        assert_eq!(expect.len(), flat.len());
+        for (expect, actual) in expect.iter().zip(&flat) {
+            assert!((expect.x - actual.x).abs() < 1e-12 && (expect.y - actual.y).abs() < 1e-12);
+            assert_eq!(0.0, actual.z);
+        }
+    }
+
+    #[test]
+    fn test_yaw_spreads_branches() {
+        let chart = SpatialBinaryTree {}.generate_chart(config(4));
+        assert_eq!(chart.orders.len() + 1, chart.points.len());
+        // the first children stay in the initial plane, grandchildren leave it
+        assert!(chart.points[2..4].iter().all(|p| p.z.abs() < 1e-12));
+        assert!(chart.points[4..8].iter().all(|p| p.z.abs() > 1e-3));
+    }
+
+    #[test]
+    fn test_yaw_parameter() {
+        let flat = SpatialBinaryTree {}.generate_base_points(SpatialChartConfig {
+            parameters: Some(SpatialKindParameters::BinaryTree(SpatialTreeParameters {
+                yaw: 0.0,
+            })),
+            ..config(4)
+        });
+        assert_eq!(SpatialBinaryTree::generate(config(4), 0.0), flat);
+        assert!(flat.iter().all(|p| p.z == 0.0));
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{
+    model::shape::{Order, Point3, SpatialChartConfig},
+    repository::shapes::{
+        generator::orders::{OrderConfig, OrderKind, OrdersGenerator, OrdersGeneratorImpl},
+        kinds::traits::SpatialShapesGenerator,
+    },
+};
+
+use super::lattice::LevelMutation;
+
+// the mutation scales and twists each halving of the cube, see `LevelMutation`
+pub struct HilbertCurve;
+
+impl HilbertCurve {
+    // higher complexities are drawn at this one
+    pub const MAX_COMPLEXITY: u64 = 5;
+
+    fn bits(complexity: u64) -> u32 {
+        complexity.clamp(1, HilbertCurve::MAX_COMPLEXITY) as u32
+    }
+
+    // Skilling's transform from a Hilbert index to cube coordinates, each `bits` wide
+    fn coordinates(index: u64, bits: u32) -> [u64; 3] {
+        let mut x = [0_u64; 3];
+        for k in 0..3 * bits {
+            let bit = (index >> (3 * bits - 1 - k)) & 1;
+            x[(k % 3) as usize] |= bit << (bits - 1 - k / 3);
+        }
+        let t = x[2] >> 1;
+        for i in (1..3).rev() {
+            x[i] ^= x[i - 1];
+        }
+        x[0] ^= t;
+        let mut q = 2;
+        while q != 1 << bits {
+            let p = q - 1;
+            for i in (0..3).rev() {
+                if x[i] & q != 0 {
+                    x[0] ^= p;
+                } else {
+                    let t = (x[0] ^ x[i]) & p;
+                    x[0] ^= t;
+                    x[i] ^= t;
+                }
+            }
+            q <<= 1;
+        }
+        x
+    }
+}
+
+impl SpatialShapesGenerator for HilbertCurve {
+    fn generate_base_points(&self, config: SpatialChartConfig) -> Vec<Point3> {
+        let bits = HilbertCurve::bits(config.complexity);
+        let side = ((1_u64 << bits) - 1) as f64;
+        let mutation = LevelMutation::new(&config, bits as usize);
+        // the offsets add up to the cell's distance from the centre of the cube
+        let scale = |value: f64| 2.0 * (value + side / 2.0) / side - 1.0;
+        (0..1_u64 << (3 * bits))
+            .map(|index| {
+                let cell = HilbertCurve::coordinates(index, bits);
+                let offsets: Vec<Point3> = (0..bits)
+                    .rev()
+                    .map(|level| {
+                        let offset = |value: u64| {
+                            ((value >> level & 1) as f64 - 0.5) * (1_u64 << level) as f64
+                        };
+                        Point3 {
+                            x: offset(cell[0]),
+                            y: offset(cell[1]),
+                            z: offset(cell[2]),
+                        }
+                    })
+                    .collect();
+                let point = mutation.apply(&offsets);
+                Point3 {
+                    x: scale(point.x),
+                    y: scale(point.y),
+                    z: scale(point.z),
+                }
+            })
+            .collect()
+    }
+
+    fn generate_orders(&self, complexity: u64) -> Vec<Order> {
+        OrdersGeneratorImpl::generate(OrderConfig {
+            kind: OrderKind::Linear,
+            point_count: 1 << (3 * HilbertCurve::bits(complexity)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::model::shape::{Mutation, Randomizer, SpatialChartKind};
+
+    #[test]
+    fn test_hilbert_curve_visits_every_cell_once() {
+        for complexity in 1..=3 {
+            let bits = HilbertCurve::bits(complexity);
+            let cells: Vec<[u64; 3]> = (0..1 << (3 * bits))
+                .map(|index| HilbertCurve::coordinates(index, bits))
+                .collect();
+            let unique: HashSet<[u64; 3]> = cells.iter().copied().collect();
+            assert_eq!(cells.len(), unique.len());
+            for pair in cells.windows(2) {
+                let step: u64 = (0..3).map(|i| pair[0][i].abs_diff(pair[1][i])).sum();
+                assert_eq!(1, step);
+            }
+        }
+        let chart = HilbertCurve {}.generate_chart(SpatialChartConfig {
+            kind: SpatialChartKind::HilbertCurve,
+            complexity: 2,
+            mutation: None,
+            randomizer: None,
+            parameters: None,
+        });
+        assert_eq!(64, chart.points.len());
+        assert_eq!(63, chart.orders.len());
+        assert_eq!(
+            Point3 {
+                x: -1.0,
+                y: -1.0,
+                z: -1.0
+            },
+            chart.points[0]
+        );
+    }
+
+    #[test]
+    fn test_hilbert_curve_mutation() {
+        let generate = |mutation, randomizer| {
+            HilbertCurve {}.generate_base_points(SpatialChartConfig {
+                kind: SpatialChartKind::HilbertCurve,
+                complexity: 3,
+                mutation,
+                randomizer,
+                parameters: None,
+            })
+        };
+        let plain = generate(None, None);
+        let still = Randomizer {
+            size_amplitude: 0.0,
+            size_seed: 0,
+            angle_amplitude: 0.0,
+            angle_seed: 0,
+        };
+        let identity = Some(Mutation {
+            size: 1.0,
+            angle: 1.0,
+        });
+        assert_eq!(plain, generate(identity, Some(still)));
+
+        // quarter turns at every level move the points along other paths through the same cells
+        let twisted = generate(
+            Some(Mutation {
+                size: 1.0,
+                angle: 2.0,
+            }),
+            None,
+        );
+        assert_ne!(plain, twisted);
+        let sorted = |points: &Vec<Point3>| {
+            let mut cells: Vec<[i64; 3]> = points
+                .iter()
+                .map(|p| [p.x, p.y, p.z].map(|value| (value * 7.0).round() as i64))
+                .collect();
+            cells.sort();
+            cells
+        };
+        assert_eq!(sorted(&plain), sorted(&twisted));
+
+        // shrinking every level keeps the curve inside the cube, and the randomizer varies it by seed
+        let shrunk = Some(Mutation {
+            size: 0.9,
+            angle: 1.0,
+        });
+        assert!(generate(shrunk, None)
+            .iter()
+            .all(|p| p.x.abs() < 1.0 && p.y.abs() < 1.0 && p.z.abs() < 1.0));
+        let noisy = |size_seed| {
+            generate(
+                shrunk,
+                Some(Randomizer {
+                    size_amplitude: 0.1,
+                    size_seed,
+                    ..still
+                }),
+            )
+        };
+        assert_eq!(noisy(1), noisy(1));
+        assert_ne!(noisy(1), noisy(2));
+    }
+}
@@ -0,0 +1,58 @@
+use std::f64::consts::FRAC_PI_2;
+
+use crate::{
+    model::shape::{Mutation, Point3, SpatialChartConfig},
+    repository::shapes::generator::randomizer::RandomGenerator,
+};
+
+// scale and twist of each subdivision level, coarsest first, drawn like the fold curves draw theirs;
+// every level also carries the ones above it, and without a mutation nothing changes
+pub struct LevelMutation {
+    levels: Vec<(f64, f64)>,
+}
+
+impl LevelMutation {
+    pub fn new(config: &SpatialChartConfig, count: usize) -> Self {
+        let mutation = config.mutation.unwrap_or(Mutation {
+            size: 1.0,
+            angle: 1.0,
+        });
+        let (mut size_randomizer, mut angle_randomizer) = match config.randomizer {
+            Some(randomizer) => (
+                RandomGenerator::new(randomizer.size_seed, randomizer.size_amplitude),
+                RandomGenerator::new(randomizer.angle_seed, randomizer.angle_amplitude),
+            ),
+            None => (RandomGenerator::new(0, 0.0), RandomGenerator::new(0, 0.0)),
+        };
+        let mut scale = 1.0;
+        let mut twist = 0.0;
+        let levels = (0..count)
+            .map(|_| {
+                scale *= size_randomizer.generate() + mutation.size;
+                // the angle factor past one, in quarter turns about the z axis
+                twist += (angle_randomizer.generate() + mutation.angle - 1.0) * FRAC_PI_2;
+                (scale, twist)
+            })
+            .collect();
+        LevelMutation { levels }
+    }
+
+    // sum of one offset per level, each from the centre of the cell above it
+    pub fn apply(&self, offsets: &[Point3]) -> Point3 {
+        offsets.iter().zip(&self.levels).fold(
+            Point3 {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            |sum, (offset, &(scale, twist))| {
+                let (sin, cos) = twist.sin_cos();
+                Point3 {
+                    x: sum.x + scale * (offset.x * cos - offset.y * sin),
+                    y: sum.y + scale * (offset.x * sin + offset.y * cos),
+                    z: sum.z + scale * offset.z,
+                }
+            },
+        )
+    }
+}
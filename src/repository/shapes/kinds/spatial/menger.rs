@@ -0,0 +1,222 @@
+use std::{
+    cmp,
+    collections::{HashMap, HashSet},
+};
+
+use crate::{
+    model::shape::{Order, Point3, SpatialChartConfig},
+    repository::shapes::kinds::traits::SpatialShapesGenerator,
+};
+
+use super::lattice::LevelMutation;
+
+type Lattice = (i64, i64, i64);
+
+// edges of the faces left exposed on the sponge's surface, tunnels included; the mutation scales and
+// twists each subdivision, see `LevelMutation`
+pub struct MengerSponge;
+
+impl MengerSponge {
+    // higher complexities are drawn at this one
+    pub const MAX_COMPLEXITY: u64 = 4;
+
+    fn level(complexity: u64) -> u32 {
+        cmp::min(complexity, MengerSponge::MAX_COMPLEXITY).saturating_sub(1) as u32
+    }
+
+    // offsets of a lattice coordinate from the centre of each enclosing cube, coarsest first, then from
+    // the centre of its unit cube; points on the far faces count as corners of the last cubes
+    fn offsets(value: i64, level: u32) -> Vec<f64> {
+        let side = 3_i64.pow(level);
+        let cube = value.min(side - 1);
+        (0..level)
+            .rev()
+            .map(|l| {
+                let unit = 3_i64.pow(l);
+                (cube / unit % 3 - 1) as f64 * unit as f64
+            })
+            .chain([(value - cube) as f64 - 0.5])
+            .collect()
+    }
+
+    // unit cubes kept after `level` subdivisions, in lattice coordinates
+    fn cubes(level: u32) -> Vec<Lattice> {
+        let mut cubes: Vec<Lattice> = vec![(0, 0, 0)];
+        for _ in 0..level {
+            cubes = cubes
+                .iter()
+                .flat_map(|&(x, y, z)| {
+                    (0..27).filter_map(move |i| {
+                        let (dx, dy, dz) = (i % 3, i / 3 % 3, i / 9);
+                        let centred = [dx, dy, dz].iter().filter(|&&d| d == 1).count();
+                        if centred > 1 {
+                            return None;
+                        }
+                        Some((3 * x + dx, 3 * y + dy, 3 * z + dz))
+                    })
+                })
+                .collect();
+        }
+        cubes.sort();
+        cubes
+    }
+
+    // lattice points and edges of every exposed face, numbered in order of appearance
+    fn surface(level: u32) -> (Vec<Lattice>, Vec<(u64, u64)>) {
+        let cubes = MengerSponge::cubes(level);
+        let kept: HashSet<Lattice> = cubes.iter().copied().collect();
+        let mut indices: HashMap<Lattice, u64> = HashMap::new();
+        let mut points: Vec<Lattice> = vec![];
+        let mut edges: Vec<(u64, u64)> = vec![];
+        let mut seen: HashSet<(u64, u64)> = HashSet::new();
+        let mut index = |p: Lattice, points: &mut Vec<Lattice>| {
+            *indices.entry(p).or_insert_with(|| {
+                points.push(p);
+                points.len() as u64 - 1
+            })
+        };
+        for &(x, y, z) in &cubes {
+            for axis in 0..3 {
+                for side in [0, 1] {
+                    let mut neighbour = [x, y, z];
+                    neighbour[axis] += 2 * side - 1;
+                    if kept.contains(&(neighbour[0], neighbour[1], neighbour[2])) {
+                        continue;
+                    }
+                    // the face's corners walk around it
+                    let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+                    let corners: Vec<Lattice> = [(0, 0), (1, 0), (1, 1), (0, 1)]
+                        .iter()
+                        .map(|&(du, dv)| {
+                            let mut corner = [x, y, z];
+                            corner[axis] += side;
+                            corner[u] += du;
+                            corner[v] += dv;
+                            (corner[0], corner[1], corner[2])
+                        })
+                        .collect();
+                    for i in 0..4 {
+                        let a = index(corners[i], &mut points);
+                        let b = index(corners[(i + 1) % 4], &mut points);
+                        if seen.insert((a.min(b), a.max(b))) {
+                            edges.push((a, b));
+                        }
+                    }
+                }
+            }
+        }
+        (points, edges)
+    }
+}
+
+impl SpatialShapesGenerator for MengerSponge {
+    fn generate_base_points(&self, config: SpatialChartConfig) -> Vec<Point3> {
+        let level = MengerSponge::level(config.complexity);
+        let side = 3_i64.pow(level) as f64;
+        let mutation = LevelMutation::new(&config, level as usize + 1);
+        let scale = |value: f64| 2.0 * (value + side / 2.0) / side - 1.0;
+        MengerSponge::surface(level)
+            .0
+            .iter()
+            .map(|&(x, y, z)| {
+                let [x, y, z] = [x, y, z].map(|value| MengerSponge::offsets(value, level));
+                let offsets: Vec<Point3> = (0..x.len())
+                    .map(|l| Point3 {
+                        x: x[l],
+                        y: y[l],
+                        z: z[l],
+                    })
+                    .collect();
+                let point = mutation.apply(&offsets);
+                Point3 {
+                    x: scale(point.x),
+                    y: scale(point.y),
+                    z: scale(point.z),
+                }
+            })
+            .collect()
+    }
+
+    fn generate_orders(&self, complexity: u64) -> Vec<Order> {
+        MengerSponge::surface(MengerSponge::level(complexity))
+            .1
+            .iter()
+            .map(|&link| Order { link })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::shape::{Mutation, SpatialChartKind};
+
+    #[test]
+    fn test_menger_sponge_generation() {
+        assert_eq!(20, MengerSponge::cubes(1).len());
+        assert_eq!(400, MengerSponge::cubes(2).len());
+
+        let generate = |complexity: u64| {
+            MengerSponge {}.generate_chart(SpatialChartConfig {
+                kind: SpatialChartKind::MengerSponge,
+                complexity,
+                mutation: None,
+                randomizer: None,
+                parameters: None,
+            })
+        };
+        let cube = generate(1);
+        assert_eq!(8, cube.points.len());
+        assert_eq!(12, cube.orders.len());
+
+        // the first level exposes every lattice point but hides no edge of the full 3 x 3 x 3 grid
+        let sponge = generate(2);
+        assert_eq!(64, sponge.points.len());
+        assert_eq!(144, sponge.orders.len());
+        for order in &sponge.orders {
+            let (a, b) = (
+                sponge.points[order.link.0 as usize],
+                sponge.points[order.link.1 as usize],
+            );
+            let length = ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt();
+            assert!((length - 2.0 / 3.0).abs() < 1e-12);
+        }
+
+        // deeper levels hide the edges between neighbouring cubes
+        let deeper = generate(3);
+        let full_grid = 3 * 10 * 10 * 9;
+        assert!(deeper.orders.len() < full_grid);
+    }
+
+    #[test]
+    fn test_menger_sponge_mutation() {
+        let generate = |mutation| {
+            MengerSponge {}.generate_chart(SpatialChartConfig {
+                kind: SpatialChartKind::MengerSponge,
+                complexity: 3,
+                mutation,
+                randomizer: None,
+                parameters: None,
+            })
+        };
+        let plain = generate(None);
+        let identity = generate(Some(Mutation {
+            size: 1.0,
+            angle: 1.0,
+        }));
+        assert_eq!(plain, identity);
+        // a twist moves the points but keeps the edges between them
+        let twisted = generate(Some(Mutation {
+            size: 1.0,
+            angle: 1.5,
+        }));
+        assert_eq!(plain.orders, twisted.orders);
+        assert_ne!(plain.points, twisted.points);
+
+        let shrunk = generate(Some(Mutation {
+            size: 0.8,
+            angle: 1.0,
+        }));
+        assert!(shrunk.points.iter().all(|p| p.x.abs() < 1.0));
+    }
+}
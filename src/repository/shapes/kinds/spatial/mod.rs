@@ -0,0 +1,4 @@
+pub mod binarytree;
+pub mod hilbert;
+pub mod lattice;
+pub mod menger;
@@ -1,5 +1,8 @@
 use super::traits::ShapesGenerator;
-use crate::model::shape::{BaseChartConfig, Order, Point};
+use crate::model::{
+    shape::{BaseChartConfig, Order, Point},
+    transform::Transform,
+};
 use crate::repository::shapes::generator::orders::{
     OrderConfig, OrderKind, OrdersGenerator, OrdersGeneratorImpl,
 };
@@ -11,10 +14,12 @@ pub struct Star;
 
 impl ShapesGenerator for Star {
     fn generate_base_points(self: &Self, config: BaseChartConfig) -> Vec<Point> {
-        return PointsGeneratorImpl::generate(PointsConfig {
+        let radius = config.circle_parameters().radius;
+        let points = PointsGeneratorImpl::generate(PointsConfig {
             kind: PointKind::Circle,
             length: config.complexity,
         });
+        Transform::scale(radius, radius).apply_points(&points)
     }
 
     fn generate_orders(self: &Self, complexity: u64) -> Vec<Order> {
@@ -39,6 +44,7 @@ mod tests {
             kind: ChartKind::Star,
             mutation: None,
             randomizer: None,
+            parameters: None,
         });
         let expect = vec![
             Point { x: 0.0, y: 1.0 },
@@ -17,10 +17,15 @@ impl Starmine {
 impl ShapesGenerator for Starmine {
     fn generate_base_points(self: &Self, config: BaseChartConfig) -> Vec<Point> {
         let point_count = Starmine::point_count(config.complexity);
+        let radii = config.starmine_parameters();
         (0..point_count)
             .map(|i| {
                 let angle = (2.0 * PI * i as f64) / point_count as f64 - PI;
-                let amplitude = if i % 2 == 0 { 1.0 } else { 0.25 };
+                let amplitude = if i % 2 == 0 {
+                    radii.outer_radius
+                } else {
+                    radii.inner_radius
+                };
                 Point {
                     x: amplitude * angle.cos(),
                     y: amplitude * angle.sin(),
@@ -40,7 +45,7 @@ impl ShapesGenerator for Starmine {
 
 #[cfg(test)]
 mod tests {
-    use crate::model::shape::ChartKind;
+    use crate::model::shape::{ChartKind, KindParameters, StarmineParameters};
 
     use super::*;
 
@@ -52,6 +57,7 @@ mod tests {
             kind: ChartKind::Starmine,
             mutation: None,
             randomizer: None,
+            parameters: None,
         });
         let expect = vec![
             Point {
@@ -96,4 +102,36 @@ mod tests {
         ];
         assert_eq!(expect, actual);
     }
+
+    #[test]
+    fn test_parameters() {
+        let chart = Starmine {};
+        let config = |parameters| BaseChartConfig {
+            complexity: 4,
+            kind: ChartKind::Starmine,
+            mutation: None,
+            randomizer: None,
+            parameters,
+        };
+        let defaults = Some(KindParameters::Starmine(StarmineParameters::default()));
+        assert_eq!(
+            chart.generate_chart(config(None)),
+            chart.generate_chart(config(defaults))
+        );
+
+        let wide = chart.generate_base_points(config(Some(KindParameters::Starmine(
+            StarmineParameters {
+                outer_radius: 2.0,
+                inner_radius: 0.5,
+            },
+        ))));
+        assert_eq!(Point { x: -2.0, y: 0.0 }, wide[0]);
+        assert_eq!(
+            Point {
+                x: 0.5 * (-0.75 * PI).cos(),
+                y: 0.5 * (-0.75 * PI).sin(),
+            },
+            wide[1]
+        );
+    }
 }
@@ -0,0 +1,91 @@
+use super::traits::ShapesGenerator;
+use crate::model::{
+    shape::{BaseChartConfig, ChartKind, Order, Point},
+    transform::Transform,
+};
+use crate::repository::shapes::generator::orders::{
+    OrderConfig, OrderKind, OrdersGenerator, OrdersGeneratorImpl,
+};
+use crate::repository::shapes::generator::points::{
+    PointKind, PointsConfig, PointsGenerator, PointsGeneratorImpl,
+};
+
+pub struct TimesTable {
+    pub kind: ChartKind,
+}
+
+impl TimesTable {
+    fn select_multiplier(kind: &ChartKind) -> f64 {
+        match kind {
+            ChartKind::Cardioid => 2.0,
+            ChartKind::Nephroid => 3.0,
+            ChartKind::Deltoid => -2.0,
+            _ => panic!("Unsupported chart kind!"),
+        }
+    }
+}
+
+impl ShapesGenerator for TimesTable {
+    fn generate_base_points(&self, config: BaseChartConfig) -> Vec<Point> {
+        let radius = config.circle_parameters().radius;
+        let points = PointsGeneratorImpl::generate(PointsConfig {
+            kind: PointKind::Circle,
+            length: config.complexity,
+        });
+        Transform::scale(radius, radius).apply_points(&points)
+    }
+
+    fn generate_orders(&self, complexity: u64) -> Vec<Order> {
+        OrdersGeneratorImpl::generate(OrderConfig {
+            kind: OrderKind::TimesTable {
+                start_multiplier: 1.0,
+                end_multiplier: TimesTable::select_multiplier(&self.kind),
+                offset: 0.0,
+                step: 0.0,
+            },
+            point_count: complexity,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_points_generation() {
+        let chart = TimesTable {
+            kind: ChartKind::Cardioid,
+        };
+        let actual = chart.generate_base_points(BaseChartConfig {
+            complexity: 4,
+            kind: ChartKind::Cardioid,
+            mutation: None,
+            randomizer: None,
+            parameters: None,
+        });
+        let expect = vec![
+            Point { x: 0.0, y: 1.0 },
+            Point { x: 1.0, y: 0.0 },
+            Point { x: 0.0, y: -1.0 },
+            Point { x: -1.0, y: 0.0 },
+        ];
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn test_orders_generation() {
+        let chart = TimesTable {
+            kind: ChartKind::Deltoid,
+        };
+        let actual = chart.generate_orders(5);
+        let expect = vec![
+            Order { link: (0, 0) },
+            Order { link: (1, 3) },
+            Order { link: (2, 1) },
+            Order { link: (3, 4) },
+            Order { link: (4, 2) },
+        ];
+        assert_eq!(expect, actual);
+    }
+}
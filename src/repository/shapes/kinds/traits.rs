@@ -1,6 +1,56 @@
-use crate::model::shape::{BaseChartConfig, Order, Point};
+use crate::model::shape::{
+    AttributedChart, BaseChartConfig, Chart, Chart3, Order, OrderAttributes, Point, Point3,
+    PointAttributes, SpatialChartConfig,
+};
 
 pub trait ShapesGenerator {
     fn generate_base_points(self: &Self, config: BaseChartConfig) -> Vec<Point>;
+    // orders under the kind's default parameters; where the parameters change the point count,
+    // as for Clover and the parametric curves, only `generate_chart` pairs the orders with the points
     fn generate_orders(self: &Self, complexity: u64) -> Vec<Order>;
+
+    fn generate_chart(&self, config: BaseChartConfig) -> Chart {
+        let orders = self.generate_orders(config.complexity);
+        Chart {
+            points: self.generate_base_points(config),
+            orders,
+        }
+    }
+
+    // kinds without a generation structure report every element at depth 0
+    fn generate_attributed_chart(&self, config: BaseChartConfig) -> AttributedChart {
+        let chart = self.generate_chart(config);
+        AttributedChart {
+            point_attributes: chart
+                .points
+                .iter()
+                .map(|_| PointAttributes {
+                    depth: 0,
+                    parent: None,
+                })
+                .collect(),
+            order_attributes: (0..chart.orders.len() as u64)
+                .map(|division| OrderAttributes {
+                    depth: 0,
+                    division,
+                    parent: None,
+                    rule: None,
+                })
+                .collect(),
+            chart,
+        }
+    }
+}
+
+pub trait SpatialShapesGenerator {
+    fn generate_base_points(&self, config: SpatialChartConfig) -> Vec<Point3>;
+    fn generate_orders(&self, complexity: u64) -> Vec<Order>;
+
+    fn generate_chart(&self, config: SpatialChartConfig) -> Chart3 {
+        let orders = self.generate_orders(config.complexity);
+        Chart3 {
+            points: self.generate_base_points(config),
+            orders,
+        }
+    }
 }
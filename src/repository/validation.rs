@@ -0,0 +1,585 @@
+use std::{fmt, mem, time::Duration};
+
+use crate::{
+    model::shape::{
+        BaseChartConfig, ChartKind, KindParameters, Mutation, Order, Point, Randomizer,
+        SpatialChartConfig, SpatialChartKind, SpatialKindParameters,
+    },
+    repository::shapes::{
+        generator::curve::{CurveSampler, Refinement},
+        kinds::{
+            binarytree::BinaryTree,
+            clover::Clover,
+            parametric::Parametric,
+            spatial::{hilbert::HilbertCurve, menger::MengerSponge},
+        },
+    },
+};
+
+// rough single-threaded generation cost per point, by how much work each point takes
+const NANOS_PER_DIRECT_POINT: u64 = 20;
+const NANOS_PER_TREE_POINT: u64 = 40;
+const NANOS_PER_FOLD_POINT: u64 = 80;
+// parametric curves whose period needs more starting segments are rejected, e.g. roses with d above 256
+const MAX_CURVE_SEGMENTS: u64 = 1 << 12;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostEstimate {
+    pub points: u64,
+    pub orders: u64,
+    // points and orders held in the finished chart
+    pub bytes: u64,
+    pub time: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GenerationBudget {
+    pub max_points: u64,
+    pub max_bytes: u64,
+    pub max_time: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValidationError {
+    UnsupportedKind(ChartKind),
+    ComplexityTooLow { minimum: u64 },
+    // the generator would clamp it and draw a smaller chart than asked for
+    ComplexityTooHigh { maximum: u64 },
+    MissingMutation,
+    MissingRandomizer,
+    NonFiniteValue,
+    NegativeAmplitude,
+    // parameters meant for another kind, which the generator would ignore
+    MismatchedParameters,
+    InvalidParameters,
+    // the counts do not fit in 64 bits
+    Overflow,
+    OverBudget(CostEstimate),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationError::UnsupportedKind(kind) => write!(f, "{:?} has no generator", kind),
+            ValidationError::ComplexityTooLow { minimum } => {
+                write!(f, "complexity must be at least {}", minimum)
+            }
+            ValidationError::ComplexityTooHigh { maximum } => {
+                write!(f, "complexity must be at most {}", maximum)
+            }
+            ValidationError::MissingMutation => write!(f, "kind requires a mutation"),
+            ValidationError::MissingRandomizer => write!(f, "kind requires a randomizer"),
+            ValidationError::NonFiniteValue => write!(f, "values must be finite"),
+            ValidationError::NegativeAmplitude => {
+                write!(f, "randomizer amplitudes must not be negative")
+            }
+            ValidationError::MismatchedParameters => {
+                write!(f, "parameters do not belong to this kind")
+            }
+            ValidationError::InvalidParameters => {
+                write!(f, "parameters are out of range for this kind")
+            }
+            ValidationError::Overflow => write!(f, "complexity is too large to count"),
+            ValidationError::OverBudget(estimate) => write!(
+                f,
+                "{} points, {} bytes and {:?} exceed the budget",
+                estimate.points, estimate.bytes, estimate.time
+            ),
+        }
+    }
+}
+
+pub struct ConfigValidator;
+
+impl ConfigValidator {
+    fn is_folded(kind: &ChartKind) -> bool {
+        matches!(
+            kind,
+            ChartKind::FoldCCurve
+                | ChartKind::FoldDragon
+                | ChartKind::KochCurve
+                | ChartKind::KochTriangleInner
+                | ChartKind::KochTriangleOuter
+                | ChartKind::TriCis
+                | ChartKind::TriTrans
+        )
+    }
+
+    // `segments_per_fold` to the power of the number of folds, plus the closing point
+    fn folded_points(segments_per_fold: u64, complexity: u64) -> Option<u64> {
+        let folds = u32::try_from(complexity - 1).ok()?;
+        segments_per_fold.checked_pow(folds)?.checked_add(1)
+    }
+
+    // the point and order counts each generator produces
+    fn counts(config: &BaseChartConfig) -> Option<(u64, u64)> {
+        let c = config.complexity;
+        match config.kind {
+            ChartKind::BinaryTree => {
+                let points = BinaryTree::point_count(c);
+                Some((points, points - 1))
+            }
+            ChartKind::Cardioid
+            | ChartKind::Deltoid
+            | ChartKind::Nephroid
+            | ChartKind::Star
+            | ChartKind::Sunrise => Some((c, c)),
+            ChartKind::Clover => {
+                match Refinement::chord_deviation(config.clover_parameters().tolerance) {
+                    None => c
+                        .checked_mul(config.clover_parameters().density)
+                        .map(|points| (points, points)),
+                    // adaptive sampling is walked to count it, once its starting grid is known to be small
+                    Some(refinement) => {
+                        let curve = Clover::curve(c);
+                        if CurveSampler::initial_segments(&curve) > MAX_CURVE_SEGMENTS {
+                            return None;
+                        }
+                        let points = CurveSampler::count(&curve, refinement);
+                        Some((points, points))
+                    }
+                }
+            }
+            ChartKind::Starmine => c.checked_mul(2).map(|points| (points, points)),
+            ChartKind::FoldCCurve | ChartKind::FoldDragon => {
+                ConfigValidator::folded_points(2, c).map(|points| (points, points - 1))
+            }
+            ChartKind::KochCurve | ChartKind::TriCis | ChartKind::TriTrans => {
+                ConfigValidator::folded_points(4, c).map(|points| (points, points - 1))
+            }
+            ChartKind::KochTriangleInner | ChartKind::KochTriangleOuter => {
+                let points = ConfigValidator::folded_points(4, c)?.checked_mul(3)?;
+                Some((points, points - 3))
+            }
+            // the sampler follows the curvature, so parametric curves are walked to count them
+            ChartKind::Rose
+            | ChartKind::Hypotrochoid
+            | ChartKind::Epitrochoid
+            | ChartKind::Superformula => {
+                let points = CurveSampler::count(
+                    Parametric::curve(config).as_ref(),
+                    Parametric::refinement(config),
+                );
+                Some((points, points))
+            }
+            ChartKind::Sunset => None,
+        }
+    }
+
+    fn check_parameters(
+        config: &BaseChartConfig,
+        parameters: &KindParameters,
+    ) -> Result<(), ValidationError> {
+        let kind = &config.kind;
+        let valid = |value: f64| value.is_finite() && value >= 0.0;
+        let positive = |value: f64| value.is_finite() && value > 0.0;
+        // checked last, once the ratios are known not to divide by zero
+        let short = || {
+            CurveSampler::initial_segments(Parametric::curve(config).as_ref()) <= MAX_CURVE_SEGMENTS
+        };
+        let fits = match parameters {
+            KindParameters::Circle(circle) => matches!(
+                kind,
+                ChartKind::Cardioid
+                    | ChartKind::Deltoid
+                    | ChartKind::Nephroid
+                    | ChartKind::Star
+                    | ChartKind::Sunrise
+            )
+            .then_some(valid(circle.radius)),
+            KindParameters::Clover(clover) => (*kind == ChartKind::Clover)
+                .then_some(clover.density > 0 && clover.tolerance.is_none_or(valid)),
+            KindParameters::KochTriangle(triangle) => matches!(
+                kind,
+                ChartKind::KochTriangleInner | ChartKind::KochTriangleOuter
+            )
+            .then_some(valid(triangle.offset)),
+            KindParameters::Starmine(starmine) => (*kind == ChartKind::Starmine)
+                .then_some(valid(starmine.outer_radius) && valid(starmine.inner_radius)),
+            KindParameters::Rose(rose) => (*kind == ChartKind::Rose).then(|| {
+                rose.numerator > 0
+                    && rose.denominator > 0
+                    && rose.tolerance.is_none_or(valid)
+                    && short()
+            }),
+            KindParameters::Trochoid(trochoid) => {
+                matches!(kind, ChartKind::Hypotrochoid | ChartKind::Epitrochoid).then(|| {
+                    trochoid.fixed_radius > 0
+                        && trochoid.rolling_radius > 0
+                        && valid(trochoid.pen_distance)
+                        && trochoid.tolerance.is_none_or(valid)
+                        && short()
+                })
+            }
+            KindParameters::Superformula(superformula) => {
+                (*kind == ChartKind::Superformula).then(|| {
+                    superformula.m_numerator > 0
+                        && superformula.m_denominator > 0
+                        && superformula.n1.is_finite()
+                        && superformula.n1 != 0.0
+                        && superformula.n2.is_finite()
+                        && superformula.n3.is_finite()
+                        && positive(superformula.a)
+                        && positive(superformula.b)
+                        && superformula.tolerance.is_none_or(valid)
+                        && short()
+                })
+            }
+        };
+        match fits {
+            None => Err(ValidationError::MismatchedParameters),
+            Some(false) => Err(ValidationError::InvalidParameters),
+            Some(true) => Ok(()),
+        }
+    }
+
+    fn check_variation(
+        mutation: &Option<Mutation>,
+        randomizer: &Option<Randomizer>,
+    ) -> Result<(), ValidationError> {
+        if let Some(mutation) = mutation {
+            if !mutation.size.is_finite() || !mutation.angle.is_finite() {
+                return Err(ValidationError::NonFiniteValue);
+            }
+        }
+        if let Some(randomizer) = randomizer {
+            let amplitudes = [randomizer.size_amplitude, randomizer.angle_amplitude];
+            if amplitudes.iter().any(|a| !a.is_finite()) {
+                return Err(ValidationError::NonFiniteValue);
+            }
+            if amplitudes.iter().any(|a| *a < 0.0) {
+                return Err(ValidationError::NegativeAmplitude);
+            }
+        }
+        Ok(())
+    }
+
+    // checks that generating the spatial `config` would neither panic nor clamp its complexity
+    pub fn validate_spatial(config: &SpatialChartConfig) -> Result<(), ValidationError> {
+        if config.complexity < 1 {
+            return Err(ValidationError::ComplexityTooLow { minimum: 1 });
+        }
+        let maximum = match config.kind {
+            SpatialChartKind::HilbertCurve => Some(HilbertCurve::MAX_COMPLEXITY),
+            SpatialChartKind::MengerSponge => Some(MengerSponge::MAX_COMPLEXITY),
+            SpatialChartKind::BinaryTree => None,
+        };
+        if let Some(maximum) = maximum.filter(|maximum| config.complexity > *maximum) {
+            return Err(ValidationError::ComplexityTooHigh { maximum });
+        }
+        if config.kind == SpatialChartKind::BinaryTree {
+            if config.mutation.is_none() {
+                return Err(ValidationError::MissingMutation);
+            }
+            if config.randomizer.is_none() {
+                return Err(ValidationError::MissingRandomizer);
+            }
+        }
+        ConfigValidator::check_variation(&config.mutation, &config.randomizer)?;
+        match config.parameters {
+            Some(SpatialKindParameters::BinaryTree(_))
+                if config.kind != SpatialChartKind::BinaryTree =>
+            {
+                Err(ValidationError::MismatchedParameters)
+            }
+            Some(SpatialKindParameters::BinaryTree(tree)) if !tree.yaw.is_finite() => {
+                Err(ValidationError::NonFiniteValue)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // checks that generating `config` would not panic, and what it would cost, without generating anything
+    pub fn estimate(config: &BaseChartConfig) -> Result<CostEstimate, ValidationError> {
+        if config.kind == ChartKind::Sunset {
+            return Err(ValidationError::UnsupportedKind(config.kind));
+        }
+        let folded = ConfigValidator::is_folded(&config.kind);
+        // folding starts at depth 2 and only stops on reaching the complexity
+        let minimum = match config.kind {
+            _ if folded => 2,
+            ChartKind::BinaryTree => BinaryTree::MIN_COMPLEXITY,
+            _ => 1,
+        };
+        if config.complexity < minimum {
+            return Err(ValidationError::ComplexityTooLow { minimum });
+        }
+        if config.kind == ChartKind::BinaryTree && config.complexity > BinaryTree::MAX_COMPLEXITY {
+            return Err(ValidationError::ComplexityTooHigh {
+                maximum: BinaryTree::MAX_COMPLEXITY,
+            });
+        }
+        if folded || config.kind == ChartKind::BinaryTree {
+            if config.mutation.is_none() {
+                return Err(ValidationError::MissingMutation);
+            }
+            if config.randomizer.is_none() {
+                return Err(ValidationError::MissingRandomizer);
+            }
+        }
+        ConfigValidator::check_variation(&config.mutation, &config.randomizer)?;
+
+        if let Some(parameters) = &config.parameters {
+            ConfigValidator::check_parameters(config, parameters)?;
+        }
+
+        let (points, orders) = ConfigValidator::counts(config).ok_or(ValidationError::Overflow)?;
+        let bytes = points
+            .checked_mul(mem::size_of::<Point>() as u64)
+            .zip(orders.checked_mul(mem::size_of::<Order>() as u64))
+            .and_then(|(points, orders)| points.checked_add(orders))
+            .ok_or(ValidationError::Overflow)?;
+        let nanos_per_point = if folded {
+            NANOS_PER_FOLD_POINT
+        } else if config.kind == ChartKind::BinaryTree {
+            NANOS_PER_TREE_POINT
+        } else {
+            NANOS_PER_DIRECT_POINT
+        };
+        Ok(CostEstimate {
+            points,
+            orders,
+            bytes,
+            time: Duration::from_nanos(points.saturating_mul(nanos_per_point)),
+        })
+    }
+
+    pub fn validate(
+        config: &BaseChartConfig,
+        budget: &GenerationBudget,
+    ) -> Result<CostEstimate, ValidationError> {
+        let estimate = ConfigValidator::estimate(config)?;
+        if estimate.points > budget.max_points
+            || estimate.bytes > budget.max_bytes
+            || estimate.time > budget.max_time
+        {
+            return Err(ValidationError::OverBudget(estimate));
+        }
+        Ok(estimate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::shape::{CircleParameters, CloverParameters, RoseParameters, SpatialTreeParameters},
+        repository::shapes::kinds::selector::ShapesGeneratorSelector,
+    };
+
+    fn config(kind: ChartKind, complexity: u64) -> BaseChartConfig {
+        BaseChartConfig {
+            kind,
+            complexity,
+            mutation: Some(Mutation {
+                size: 1.0,
+                angle: 1.0,
+            }),
+            randomizer: Some(Randomizer {
+                size_amplitude: 0.0,
+                size_seed: 0,
+                angle_amplitude: 0.0,
+                angle_seed: 0,
+            }),
+            parameters: None,
+        }
+    }
+
+    #[test]
+    fn test_counts_match_generation() {
+        for kind in ChartKind::ALL {
+            if kind == ChartKind::Sunset {
+                continue;
+            }
+            for complexity in [2, 3, 5] {
+                let config = config(kind, complexity);
+                let chart = ShapesGeneratorSelector::select(&kind).generate_chart(config);
+                let estimate = ConfigValidator::estimate(&config).unwrap();
+                assert_eq!(
+                    (chart.points.len() as u64, chart.orders.len() as u64),
+                    (estimate.points, estimate.orders),
+                    "{:?} at {}",
+                    kind,
+                    complexity
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_budget() {
+        let budget = GenerationBudget {
+            max_points: 1 << 20,
+            max_bytes: 64 << 20,
+            max_time: Duration::from_secs(1),
+        };
+        let dragon =
+            ConfigValidator::validate(&config(ChartKind::FoldDragon, 16), &budget).unwrap();
+        assert_eq!(32769, dragon.points);
+        assert_eq!(32769 * 16 + 32768 * 16, dragon.bytes);
+        assert!(matches!(
+            ConfigValidator::validate(&config(ChartKind::FoldDragon, 40), &budget),
+            Err(ValidationError::OverBudget(CostEstimate {
+                points: 549755813889,
+                ..
+            }))
+        ));
+        // counts that do not fit are rejected rather than wrapped
+        assert_eq!(
+            Err(ValidationError::Overflow),
+            ConfigValidator::estimate(&config(ChartKind::KochTriangleOuter, 40))
+        );
+        assert_eq!(
+            Err(ValidationError::Overflow),
+            ConfigValidator::estimate(&config(ChartKind::FoldDragon, 1 << 33))
+        );
+    }
+
+    #[test]
+    fn test_invalid_configs() {
+        assert_eq!(
+            Err(ValidationError::ComplexityTooLow { minimum: 2 }),
+            ConfigValidator::estimate(&config(ChartKind::KochCurve, 1))
+        );
+        assert_eq!(
+            Err(ValidationError::UnsupportedKind(ChartKind::Sunset)),
+            ConfigValidator::estimate(&config(ChartKind::Sunset, 3))
+        );
+        // the tree generator would clamp its levels rather than draw what was asked for
+        assert_eq!(
+            Err(ValidationError::ComplexityTooLow { minimum: 2 }),
+            ConfigValidator::estimate(&config(ChartKind::BinaryTree, 1))
+        );
+        assert_eq!(
+            Err(ValidationError::ComplexityTooHigh { maximum: 10 }),
+            ConfigValidator::estimate(&config(ChartKind::BinaryTree, 40))
+        );
+        assert_eq!(
+            2048,
+            ConfigValidator::estimate(&config(ChartKind::BinaryTree, 10))
+                .unwrap()
+                .points
+        );
+        let mut tree = config(ChartKind::BinaryTree, 3);
+        tree.randomizer = None;
+        assert_eq!(
+            Err(ValidationError::MissingRandomizer),
+            ConfigValidator::estimate(&tree)
+        );
+        let mut star = config(ChartKind::Star, 3);
+        star.mutation = Some(Mutation {
+            size: f64::NAN,
+            angle: 1.0,
+        });
+        assert_eq!(
+            Err(ValidationError::NonFiniteValue),
+            ConfigValidator::estimate(&star)
+        );
+        let mut clover = config(ChartKind::Clover, 3);
+        clover.parameters = Some(KindParameters::Clover(CloverParameters {
+            density: 100,
+            tolerance: None,
+        }));
+        assert_eq!(300, ConfigValidator::estimate(&clover).unwrap().points);
+        clover.parameters = Some(KindParameters::Clover(CloverParameters {
+            density: 0,
+            tolerance: None,
+        }));
+        assert_eq!(
+            Err(ValidationError::InvalidParameters),
+            ConfigValidator::estimate(&clover)
+        );
+        clover.parameters = Some(KindParameters::Circle(CircleParameters::default()));
+        assert_eq!(
+            Err(ValidationError::MismatchedParameters),
+            ConfigValidator::estimate(&clover)
+        );
+        // adaptive sampling follows the curve rather than the density
+        let adaptive = |tolerance| {
+            Some(KindParameters::Clover(CloverParameters {
+                density: 40,
+                tolerance: Some(tolerance),
+            }))
+        };
+        clover.parameters = adaptive(1e-3);
+        let chart = ShapesGeneratorSelector::select(&ChartKind::Clover).generate_chart(clover);
+        assert_eq!(
+            chart.points.len() as u64,
+            ConfigValidator::estimate(&clover).unwrap().points
+        );
+        clover.parameters = adaptive(-1.0);
+        assert_eq!(
+            Err(ValidationError::InvalidParameters),
+            ConfigValidator::estimate(&clover)
+        );
+        // a tolerance of 0 keeps the fixed sampling, as the schema describes it
+        clover.parameters = adaptive(0.0);
+        assert_eq!(120, ConfigValidator::estimate(&clover).unwrap().points);
+        let chart = ShapesGeneratorSelector::select(&ChartKind::Clover).generate_chart(clover);
+        assert_eq!(120, chart.points.len());
+        clover.parameters = adaptive(1e-3);
+        clover.complexity = 1 << 40;
+        assert_eq!(
+            Err(ValidationError::Overflow),
+            ConfigValidator::estimate(&clover)
+        );
+        // a rose of 1/1000 only closes after 2000π
+        let mut rose = config(ChartKind::Rose, 32);
+        rose.parameters = Some(KindParameters::Rose(RoseParameters {
+            numerator: 1,
+            denominator: 1000,
+            tolerance: None,
+        }));
+        assert_eq!(
+            Err(ValidationError::InvalidParameters),
+            ConfigValidator::estimate(&rose)
+        );
+    }
+
+    #[test]
+    fn test_spatial_configs() {
+        let spatial = |kind, complexity| SpatialChartConfig {
+            kind,
+            complexity,
+            mutation: None,
+            randomizer: None,
+            parameters: None,
+        };
+        assert_eq!(
+            Ok(()),
+            ConfigValidator::validate_spatial(&spatial(SpatialChartKind::HilbertCurve, 5))
+        );
+        // beyond these the curve and the sponge stop getting finer
+        assert_eq!(
+            Err(ValidationError::ComplexityTooHigh { maximum: 5 }),
+            ConfigValidator::validate_spatial(&spatial(SpatialChartKind::HilbertCurve, 6))
+        );
+        assert_eq!(
+            Err(ValidationError::ComplexityTooHigh { maximum: 4 }),
+            ConfigValidator::validate_spatial(&spatial(SpatialChartKind::MengerSponge, 5))
+        );
+        assert_eq!(
+            Err(ValidationError::MissingMutation),
+            ConfigValidator::validate_spatial(&spatial(SpatialChartKind::BinaryTree, 4))
+        );
+
+        let base = config(ChartKind::BinaryTree, 4);
+        let mut tree = SpatialChartConfig {
+            mutation: base.mutation,
+            randomizer: base.randomizer,
+            parameters: Some(SpatialKindParameters::BinaryTree(SpatialTreeParameters {
+                yaw: f64::NAN,
+            })),
+            ..spatial(SpatialChartKind::BinaryTree, 4)
+        };
+        assert_eq!(
+            Err(ValidationError::NonFiniteValue),
+            ConfigValidator::validate_spatial(&tree)
+        );
+        tree.kind = SpatialChartKind::MengerSponge;
+        assert_eq!(
+            Err(ValidationError::MismatchedParameters),
+            ConfigValidator::validate_spatial(&tree)
+        );
+    }
+}
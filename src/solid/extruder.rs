@@ -0,0 +1,239 @@
+use crate::{
+    analysis::geometry::GeometryAnalyzer,
+    export::strokes::StrokePlanner,
+    model::{
+        mesh::Mesh,
+        shape::{Chart, Point, Point3},
+    },
+};
+
+pub struct StrokeExtrusion {
+    // width of the wall laid along each segment
+    pub thickness: f64,
+    pub height: f64,
+}
+
+pub struct Extruder;
+
+impl Extruder {
+    fn cross(o: &Point, a: &Point, b: &Point) -> f64 {
+        (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x)
+    }
+
+    fn signed_area(polygon: &[Point]) -> f64 {
+        (0..polygon.len())
+            .map(|i| {
+                let (a, b) = (&polygon[i], &polygon[(i + 1) % polygon.len()]);
+                a.x * b.y - b.x * a.y
+            })
+            .sum::<f64>()
+            / 2.0
+    }
+
+    fn contains(triangle: [&Point; 3], p: &Point) -> bool {
+        let [a, b, c] = triangle;
+        Extruder::cross(a, b, p) >= 0.0
+            && Extruder::cross(b, c, p) >= 0.0
+            && Extruder::cross(c, a, p) >= 0.0
+    }
+
+    // ear clipping of a counter-clockwise polygon; falls back to the flattest corner when no clean ear is left
+    fn triangulate(polygon: &[Point]) -> Vec<[usize; 3]> {
+        let mut ring: Vec<usize> = (0..polygon.len()).collect();
+        let mut triangles: Vec<[usize; 3]> = vec![];
+        let mut start = 0;
+        while ring.len() > 3 {
+            let count = ring.len();
+            let corner = |k: usize| {
+                (
+                    ring[(k + count - 1) % count],
+                    ring[k],
+                    ring[(k + 1) % count],
+                )
+            };
+            let is_ear = |k: usize| {
+                let (a, b, c) = corner(k);
+                let triangle = [&polygon[a], &polygon[b], &polygon[c]];
+                Extruder::cross(triangle[0], triangle[1], triangle[2]) > 0.0
+                    && ring.iter().all(|&i| {
+                        let p = &polygon[i];
+                        triangle.contains(&p) || !Extruder::contains(triangle, p)
+                    })
+            };
+            let k = (0..count)
+                .map(|offset| (start + offset) % count)
+                .find(|&k| is_ear(k))
+                .unwrap_or_else(|| {
+                    (0..count)
+                        .max_by(|&i, &j| {
+                            let area = |k: usize| {
+                                let (a, b, c) = corner(k);
+                                Extruder::cross(&polygon[a], &polygon[b], &polygon[c])
+                            };
+                            area(i).total_cmp(&area(j))
+                        })
+                        .unwrap_or(0)
+                });
+            let (a, b, c) = corner(k);
+            triangles.push([a, b, c]);
+            ring.remove(k);
+            start = k % ring.len();
+        }
+        triangles.push([ring[0], ring[1], ring[2]]);
+        triangles
+    }
+
+    // closed solid standing on z = 0
+    fn prism(polygon: &[Point], height: f64) -> Mesh {
+        let mut polygon = polygon.to_vec();
+        if Extruder::signed_area(&polygon) < 0.0 {
+            polygon.reverse();
+        }
+        let n = polygon.len();
+        let lift = |z: f64| polygon.iter().map(move |p| Point3 { x: p.x, y: p.y, z });
+        let mut mesh = Mesh {
+            vertices: lift(0.0).chain(lift(height)).collect(),
+            triangles: vec![],
+        };
+        for [a, b, c] in Extruder::triangulate(&polygon) {
+            mesh.triangles.push([a, c, b]);
+            mesh.triangles.push([n + a, n + b, n + c]);
+        }
+        for i in 0..n {
+            let j = (i + 1) % n;
+            mesh.triangles.push([i, j, n + j]);
+            mesh.triangles.push([i, n + j, n + i]);
+        }
+        mesh
+    }
+
+    // a box around every segment; boxes are closed on their own but not merged where they overlap
+    pub fn strokes(chart: &Chart, extrusion: &StrokeExtrusion) -> Mesh {
+        let mut mesh = Mesh::new();
+        for (start, end) in GeometryAnalyzer::segments(chart) {
+            let length = (end.x - start.x).hypot(end.y - start.y);
+            if length == 0.0 {
+                continue;
+            }
+            let half = extrusion.thickness / 2.0;
+            let normal = Point {
+                x: -(end.y - start.y) / length * half,
+                y: (end.x - start.x) / length * half,
+            };
+            let corners = [
+                Point {
+                    x: start.x - normal.x,
+                    y: start.y - normal.y,
+                },
+                Point {
+                    x: end.x - normal.x,
+                    y: end.y - normal.y,
+                },
+                Point {
+                    x: end.x + normal.x,
+                    y: end.y + normal.y,
+                },
+                Point {
+                    x: start.x + normal.x,
+                    y: start.y + normal.y,
+                },
+            ];
+            mesh.append(&Extruder::prism(&corners, extrusion.height));
+        }
+        mesh
+    }
+
+    // closed chains of orders, such as loops and the Koch triangle, become solids; open strokes are skipped
+    pub fn outlines(chart: &Chart, height: f64, merge_tolerance: f64) -> Mesh {
+        let mut mesh = Mesh::new();
+        for stroke in StrokePlanner::chain(chart, merge_tolerance) {
+            if stroke.len() < 4 || stroke[0] != stroke[stroke.len() - 1] {
+                continue;
+            }
+            let polygon: Vec<Point> = stroke[..stroke.len() - 1]
+                .iter()
+                .map(|&i| chart.points[i])
+                .collect();
+            mesh.append(&Extruder::prism(&polygon, height));
+        }
+        mesh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::shape::{BaseChartConfig, ChartKind, Mutation, Order, Randomizer},
+        repository::shapes::kinds::selector::ShapesGeneratorSelector,
+    };
+
+    fn generate(kind: ChartKind, complexity: u64) -> Chart {
+        ShapesGeneratorSelector::select(&kind).generate_chart(BaseChartConfig {
+            kind,
+            complexity,
+            mutation: Some(Mutation {
+                size: 1.0,
+                angle: 1.0,
+            }),
+            randomizer: Some(Randomizer {
+                size_amplitude: 0.0,
+                size_seed: 0,
+                angle_amplitude: 0.0,
+                angle_seed: 0,
+            }),
+            parameters: None,
+        })
+    }
+
+    #[test]
+    fn test_stroke_extrusion() {
+        let chart = Chart {
+            points: vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: 4.0, y: 0.0 },
+                Point { x: 4.0, y: 3.0 },
+            ],
+            orders: vec![Order { link: (0, 1) }, Order { link: (1, 2) }],
+        };
+        let mesh = Extruder::strokes(
+            &chart,
+            &StrokeExtrusion {
+                thickness: 0.5,
+                height: 2.0,
+            },
+        );
+        assert_eq!(16, mesh.vertices.len());
+        assert_eq!(24, mesh.triangles.len());
+        assert!(mesh.is_watertight());
+        assert!((mesh.volume() - 7.0 * 0.5 * 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_closed_outlines_are_watertight() {
+        // the snowflake area is 8/5 of the initial triangle with side 2
+        let snowflake_area = 3.0_f64.sqrt() * 8.0 / 5.0;
+        for complexity in [2, 4] {
+            let mesh = Extruder::outlines(
+                &generate(ChartKind::KochTriangleOuter, complexity),
+                1.5,
+                1e-9,
+            );
+            assert!(mesh.is_watertight());
+            assert!(mesh.volume() > 0.0);
+            assert!(mesh.volume() < snowflake_area * 1.5 + 1e-9);
+        }
+        let mesh = Extruder::outlines(&generate(ChartKind::KochTriangleOuter, 6), 1.0, 1e-9);
+        assert!(mesh.is_watertight());
+        // complexity 6 subdivides five times, each adding 4/9 of the previous increment
+        let area = 3.0_f64.sqrt() * (1.0 + 0.6 * (1.0 - (4.0_f64 / 9.0).powi(5)));
+        assert!((mesh.volume() - area).abs() < 1e-9);
+
+        // the line stays open and produces nothing
+        assert!(
+            Extruder::outlines(&generate(ChartKind::FoldCCurve, 3), 1.0, 1e-9)
+                .triangles
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,2 @@
+pub mod palette;
+pub mod styler;
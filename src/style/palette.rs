@@ -0,0 +1,124 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b, a: 255 }
+    }
+
+    // `hue` in degrees, `saturation` and `value` in [0, 1]
+    pub fn hsv(hue: f64, saturation: f64, value: f64) -> Self {
+        let hue = hue.rem_euclid(360.0) / 60.0;
+        let chroma = value * saturation;
+        let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+        let (r, g, b) = match hue as u64 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = value - chroma;
+        let channel = |c: f64| ((c + m) * 255.0).round() as u8;
+        Color::rgb(channel(r), channel(g), channel(b))
+    }
+
+    fn lerp(&self, other: &Color, t: f64) -> Self {
+        let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Color {
+            r: channel(self.r, other.r),
+            g: channel(self.g, other.g),
+            b: channel(self.b, other.b),
+            a: channel(self.a, other.a),
+        }
+    }
+}
+
+// colours at positions in [0, 1], linearly interpolated in between
+pub struct Palette {
+    pub stops: Vec<(f64, Color)>,
+}
+
+impl Palette {
+    // full hue circle, as drawn along the path by the original visualizer
+    pub fn rainbow() -> Self {
+        Palette {
+            stops: (0..=6)
+                .map(|i| (i as f64 / 6.0, Color::hsv(i as f64 * 60.0, 1.0, 1.0)))
+                .collect(),
+        }
+    }
+
+    pub fn gradient(start: Color, end: Color) -> Self {
+        Palette {
+            stops: vec![(0.0, start), (1.0, end)],
+        }
+    }
+
+    pub fn sample(&self, t: f64) -> Color {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let next = self.stops.iter().position(|(position, _)| *position >= t);
+        match next {
+            None => self
+                .stops
+                .last()
+                .map(|s| s.1)
+                .unwrap_or(Color::rgb(0, 0, 0)),
+            Some(0) => self.stops[0].1,
+            Some(i) => {
+                let (start, from) = self.stops[i - 1];
+                let (end, to) = self.stops[i];
+                // stops sharing a position make a hard edge, past which the later colour holds
+                if end > start {
+                    from.lerp(&to, (t - start) / (end - start))
+                } else {
+                    to
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hsv_conversion() {
+        assert_eq!(Color::rgb(255, 0, 0), Color::hsv(0.0, 1.0, 1.0));
+        assert_eq!(Color::rgb(0, 255, 0), Color::hsv(120.0, 1.0, 1.0));
+        assert_eq!(Color::rgb(0, 128, 255), Color::hsv(210.0, 1.0, 1.0));
+        assert_eq!(Color::rgb(255, 0, 0), Color::hsv(360.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_palette_sampling() {
+        let palette = Palette::gradient(Color::rgb(0, 0, 0), Color::rgb(200, 100, 50));
+        assert_eq!(Color::rgb(0, 0, 0), palette.sample(-1.0));
+        assert_eq!(Color::rgb(100, 50, 25), palette.sample(0.5));
+        assert_eq!(Color::rgb(200, 100, 50), palette.sample(2.0));
+        assert_eq!(
+            Color::rgb(255, 255, 0),
+            Palette::rainbow().sample(1.0 / 6.0)
+        );
+
+        let black = Color::rgb(0, 0, 0);
+        let white = Color::rgb(255, 255, 255);
+        let edge = Palette {
+            stops: vec![(0.0, black), (0.5, black), (0.5, white), (1.0, white)],
+        };
+        assert_eq!(black, edge.sample(0.5));
+        assert_eq!(white, edge.sample(0.5 + 1e-9));
+        // a span without a width never divides by it
+        let broken = Palette {
+            stops: vec![(0.0, black), (f64::NAN, black), (1.0, white)],
+        };
+        assert_eq!(white, broken.sample(0.5));
+    }
+}
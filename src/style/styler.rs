@@ -0,0 +1,192 @@
+use super::palette::{Color, Palette};
+use crate::model::shape::{AttributedChart, Point};
+
+pub enum StyleSource {
+    // position of the order along the path
+    PathIndex,
+    Depth,
+    Length,
+    // direction of the segment, a full turn maps onto [0, 1]
+    Angle,
+}
+
+pub enum WidthCurve {
+    Constant(f64),
+    // from `start` at the lowest source value to `end` at the highest
+    Linear { start: f64, end: f64 },
+    // `start * ratio^value` on the raw source value, e.g. halving per depth level
+    Geometric { start: f64, ratio: f64 },
+}
+
+pub struct StyleConfig {
+    pub color_source: StyleSource,
+    pub palette: Palette,
+    pub width_source: StyleSource,
+    pub width: WidthCurve,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct StyledSegment {
+    // index into `Chart::orders`
+    pub order: usize,
+    pub start: Point,
+    pub end: Point,
+    pub color: Color,
+    pub width: f64,
+}
+
+pub struct Styler;
+
+impl Styler {
+    fn values(chart: &AttributedChart, source: &StyleSource) -> Vec<f64> {
+        let points = &chart.chart.points;
+        chart
+            .chart
+            .orders
+            .iter()
+            .enumerate()
+            .map(|(i, order)| {
+                let start = &points[chart.chart.point_index(order.link.0)];
+                let end = &points[chart.chart.point_index(order.link.1)];
+                match source {
+                    StyleSource::PathIndex => i as f64,
+                    StyleSource::Depth => chart
+                        .order_attributes
+                        .get(i)
+                        .map(|attributes| attributes.depth as f64)
+                        .unwrap_or(0.0),
+                    StyleSource::Length => (end.x - start.x).hypot(end.y - start.y),
+                    StyleSource::Angle => {
+                        let radian = (end.y - start.y).atan2(end.x - start.x);
+                        radian.rem_euclid(2.0 * std::f64::consts::PI) / (2.0 * std::f64::consts::PI)
+                    }
+                }
+            })
+            .collect()
+    }
+
+    // rescales onto [0, 1]; angles are already there
+    fn normalize(values: &[f64], source: &StyleSource) -> Vec<f64> {
+        if let StyleSource::Angle = source {
+            return values.to_vec();
+        }
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        values
+            .iter()
+            .map(|v| {
+                if max > min {
+                    (v - min) / (max - min)
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
+
+    pub fn style(chart: &AttributedChart, config: &StyleConfig) -> Vec<StyledSegment> {
+        let colors = Styler::normalize(
+            &Styler::values(chart, &config.color_source),
+            &config.color_source,
+        );
+        let raw_widths = Styler::values(chart, &config.width_source);
+        let widths = Styler::normalize(&raw_widths, &config.width_source);
+        let points = &chart.chart.points;
+
+        chart
+            .chart
+            .orders
+            .iter()
+            .enumerate()
+            .map(|(i, order)| StyledSegment {
+                order: i,
+                start: points[chart.chart.point_index(order.link.0)],
+                end: points[chart.chart.point_index(order.link.1)],
+                color: config.palette.sample(colors[i]),
+                width: match config.width {
+                    WidthCurve::Constant(width) => width,
+                    WidthCurve::Linear { start, end } => start + (end - start) * widths[i],
+                    WidthCurve::Geometric { start, ratio } => start * ratio.powf(raw_widths[i]),
+                },
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::shape::{BaseChartConfig, ChartKind, Mutation, Randomizer},
+        repository::shapes::kinds::{binarytree::BinaryTree, star::Star, traits::ShapesGenerator},
+    };
+
+    #[test]
+    fn test_path_index_styling() {
+        let chart = Star {}.generate_attributed_chart(BaseChartConfig {
+            kind: ChartKind::Star,
+            complexity: 5,
+            mutation: None,
+            randomizer: None,
+            parameters: None,
+        });
+        let actual = Styler::style(
+            &chart,
+            &StyleConfig {
+                color_source: StyleSource::PathIndex,
+                palette: Palette::gradient(Color::rgb(0, 0, 0), Color::rgb(0, 0, 200)),
+                width_source: StyleSource::PathIndex,
+                width: WidthCurve::Linear {
+                    start: 2.0,
+                    end: 1.0,
+                },
+            },
+        );
+        assert_eq!(
+            vec![0, 50, 100, 150, 200],
+            actual.iter().map(|s| s.color.b).collect::<Vec<u8>>()
+        );
+        assert_eq!(
+            vec![2.0, 1.75, 1.5, 1.25, 1.0],
+            actual.iter().map(|s| s.width).collect::<Vec<f64>>()
+        );
+        assert_eq!(chart.chart.points[2], actual[0].end);
+    }
+
+    #[test]
+    fn test_depth_styling() {
+        let chart = BinaryTree {}.generate_attributed_chart(BaseChartConfig {
+            kind: ChartKind::BinaryTree,
+            complexity: 2,
+            mutation: Some(Mutation {
+                size: 1.0,
+                angle: 1.0,
+            }),
+            randomizer: Some(Randomizer {
+                size_amplitude: 0.0,
+                size_seed: 0,
+                angle_amplitude: 0.0,
+                angle_seed: 0,
+            }),
+            parameters: None,
+        });
+        let actual = Styler::style(
+            &chart,
+            &StyleConfig {
+                color_source: StyleSource::Angle,
+                palette: Palette::rainbow(),
+                width_source: StyleSource::Depth,
+                width: WidthCurve::Geometric {
+                    start: 8.0,
+                    ratio: 0.5,
+                },
+            },
+        );
+        assert_eq!(
+            vec![4.0, 2.0, 2.0, 1.0, 1.0, 1.0, 1.0],
+            actual.iter().map(|s| s.width).collect::<Vec<f64>>()
+        );
+        // the trunk points straight up
+        assert_eq!(Palette::rainbow().sample(0.25), actual[0].color);
+    }
+}